@@ -2,3 +2,12 @@
 cargo fmt
 cargo clippy
 cargo test
+# fails to link, if the panic-free build still reaches the panic handler, see examples/panic_free.rs
+cargo rustc --example panic_free --profile panic-free --features fmt-free -- -C link-arg=-Wl,-z,defs
+# fails, if the read, write or publish path loops or recurses, see examples/hot_paths.rs
+cargo rustc --example hot_paths --profile panic-free -- --emit=llvm-ir && ./check-hot-paths.sh
+# the same with the counters of the stats feature on the drop of the guards
+cargo rustc --example hot_paths --profile panic-free --features stats -- --emit=llvm-ir && ./check-hot-paths.sh --allow-clock
+# the browser-based simulators of the firmware, see the WebAssembly section of the README
+cargo build --target wasm32-unknown-unknown
+cargo clippy --target wasm32-unknown-unknown --features std,clock
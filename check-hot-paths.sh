@@ -0,0 +1,36 @@
+#!/bin/sh
+# fails, if a hot path of examples/hot_paths.rs loops or calls a function, which returns
+# with --allow-clock, the indirect calls of the registered Clock of the `stats` feature are fine
+ir=$(ls -t target/panic-free/examples/hot_paths-*.ll | head -n 1)
+indirect=$([ "$1" = "--allow-clock" ] && echo 1 || echo 0)
+for fn in hot_read hot_write_and_publish hot_read_bounded hot_revert; do
+    # edges "from to" of the control flow graph
+    edges=$(awk -v fn="$fn" '
+        $0 ~ "^define .*@" fn "\\(" { inside = 1; block = "start"; next }
+        inside && /^}/ { inside = 0 }
+        inside && /^[^ ;].*:/ { block = $0; sub(/:.*/, "", block); gsub(/"/, "", block); next }
+        inside && /^ +(br|switch) / {
+            n = split($0, targets, "label %")
+            for (i = 2; i <= n; i++) {
+                target = targets[i]; gsub(/"/, "", target); sub(/[], ].*/, "", target); print block, target
+            }
+        }' "$ir")
+    # calls, which are not followed by unreachable
+    calls=$(awk -v fn="$fn" -v indirect="$indirect" '
+        $0 ~ "^define .*@" fn "\\(" { inside = 1; next }
+        inside && /^}/ { inside = 0 }
+        inside && call && !/^ +unreachable/ { print call }
+        inside { call = "" }
+        inside && /^ .*(call|invoke) / && !/@llvm\./ && !(indirect && / (call|invoke) [^@(]* %[^ (]+\(/) { call = $0 }' "$ir")
+    if [ -z "$edges$calls" ] && ! grep -q "^define .*@$fn(" "$ir"; then
+        echo "$fn is missing"; exit 1
+    fi
+    # tsort rejects cycles, but takes a self-loop for a single node
+    if [ -n "$edges" ] && ! echo "$edges" | tsort > /dev/null 2>&1 \
+        || echo "$edges" | awk 'NF == 2 && $1 == $2 { found = 1 } END { exit !found }'; then
+        echo "$fn loops"; exit 1
+    fi
+    if [ -n "$calls" ]; then
+        echo "$fn calls a function, which returns:"; echo "$calls"; exit 1
+    fi
+done
@@ -0,0 +1,48 @@
+//! Check of the hot paths for the stack and WCET analysis of ISRs, run by `check-hot-paths.sh`.
+//!
+//! The exported functions below get compiled to LLVM IR, in which the atomics are single instructions.
+//! The script fails, if the control flow graph of one of them contains a cycle, i.e. a loop, or if it calls a function,
+//! which returns, i.e. might recurse. Calls of the diverging contract violations and of the LLVM intrinsics are fine:
+//!
+//! `cargo rustc --example hot_paths --profile panic-free -- --emit=llvm-ir && ./check-hot-paths.sh`
+//!
+//! With `--features stats`, pass `--allow-clock` to the script, as the guards call the registered `Clock` through a pointer.
+//!
+//! Without `panic = "abort"`, e.g. in `cargo test`, it is a plain library, as `core` is precompiled with unwinding.
+#![cfg_attr(panic = "abort", no_std)]
+
+use minimal_left_right::LeftRightBuffer;
+
+static BUFFER: LeftRightBuffer<[u32; 4]> = LeftRightBuffer::new([0; 4]);
+
+/// Reads the published value.
+#[unsafe(no_mangle)]
+pub extern "C" fn hot_read() -> u32 {
+    BUFFER.read()[0]
+}
+
+/// Writes with a sync and publishes.
+#[unsafe(no_mangle)]
+pub extern "C" fn hot_write_and_publish(value: u32) {
+    let mut writer = BUFFER.write();
+    writer[0] = value;
+    BUFFER.publish(writer);
+}
+
+/// Reads with the explicitly bounded retries, which get unrolled.
+#[unsafe(no_mangle)]
+pub extern "C" fn hot_read_bounded() -> u32 {
+    BUFFER.read_bounded(3).map_or(0, |thing| thing[0])
+}
+
+/// Undoes the last publication.
+#[unsafe(no_mangle)]
+pub extern "C" fn hot_revert() -> bool {
+    BUFFER.revert().is_ok()
+}
+
+#[cfg(panic = "abort")]
+#[panic_handler]
+fn panic(_: &core::panic::PanicInfo<'_>) -> ! {
+    loop {}
+}
@@ -0,0 +1,68 @@
+//! Link-time check of the `panic-free` feature, in the style of the `no-panic` crate.
+//!
+//! The panic handler calls a function, which exists nowhere. If any exported function below still reaches the panic machinery,
+//! the handler gets linked and the build fails with an undefined symbol naming the problem:
+//!
+//! `cargo rustc --example panic_free --profile panic-free --features fmt-free -- -C link-arg=-Wl,-z,defs`
+//!
+//! The read paths are left out on purpose, as the reader count overflow of the `spin` lock can still panic.
+//! Without `panic = "abort"`, e.g. in `cargo test`, it is a plain library, as `core` is precompiled with unwinding.
+#![cfg_attr(panic = "abort", no_std)]
+
+use minimal_left_right::{CompactBuffer, LeftRightAtomic, LeftRightBuffer};
+
+static BUFFER: LeftRightBuffer<[u32; 4]> = LeftRightBuffer::new([0; 4]);
+static COMPACT: CompactBuffer<u16> = CompactBuffer::new(0);
+static ATOMIC: LeftRightAtomic<u32> = LeftRightAtomic::<u32>::new(0);
+
+/// Publishes without a sync, which reports the violations of a second writer and of a foreign guard.
+#[unsafe(no_mangle)]
+pub extern "C" fn store(value: u32) {
+    BUFFER.store([value; 4]);
+}
+
+/// Returns the code of the violation of a fallible write, or 0 on success.
+#[unsafe(no_mangle)]
+pub extern "C" fn try_store(value: u32) -> u8 {
+    match BUFFER.try_write_without_sync() {
+        Ok(mut writer) => {
+            writer[0] = value;
+            match BUFFER.try_publish(writer) {
+                Ok(()) => 0,
+                Err(error) => error.code(),
+            }
+        }
+        Err(error) => error.code(),
+    }
+}
+
+/// Writes and reads the buffer behind a single control word.
+#[unsafe(no_mangle)]
+pub extern "C" fn compact(value: u16) -> u16 {
+    let mut writer = COMPACT.write();
+    *writer = writer.wrapping_add(value);
+    COMPACT.publish(writer);
+    COMPACT.load()
+}
+
+/// Reads and writes the atomic buffer.
+#[unsafe(no_mangle)]
+pub extern "C" fn atomic(value: u32) -> u32 {
+    ATOMIC.store(value);
+    ATOMIC.load()
+}
+
+/// Personality routine referenced by the unwind tables of the precompiled `core`, which never runs with `panic = "abort"`.
+#[cfg(panic = "abort")]
+#[unsafe(no_mangle)]
+pub extern "C" fn rust_eh_personality() {}
+
+#[cfg(panic = "abort")]
+#[panic_handler]
+fn panic(_: &core::panic::PanicInfo<'_>) -> ! {
+    unsafe extern "C" {
+        fn the_panic_free_build_reaches_the_panic_handler() -> !;
+    }
+    // SAFETY: the function does not exist, so any remaining path to the panic handler fails to link.
+    unsafe { the_panic_free_build_reaches_the_panic_handler() }
+}
@@ -0,0 +1,100 @@
+//! Buffers placed in user-provided memory, see [`LeftRightArena`].
+
+use crate::LeftRightBuffer;
+use core::mem::{align_of, size_of};
+
+/// Places buffers in a user-provided memory region, e.g. if the number of channels is only known at boot.
+///
+/// Each buffer gets carved out of the remaining memory with the alignment it needs.
+/// The buffers live as long as the memory, which is never handed back, so their payloads never get dropped.
+///
+/// ```
+/// use minimal_left_right::LeftRightArena;
+///
+/// // e.g. a region of a linker section, taken once with `cortex_m::singleton!` or `static_cell`
+/// let memory: &'static mut [u8] = Box::leak(Box::new([0u8; 4096]));
+/// let mut arena = LeftRightArena::new(memory);
+/// let channels = 3;
+/// for channel in 0..channels {
+///     let buffer = arena.alloc(channel).expect("the memory holds all channels");
+///     assert_eq!(*buffer.read(), channel);
+/// }
+/// ```
+pub struct LeftRightArena {
+    memory: &'static mut [u8],
+}
+
+impl LeftRightArena {
+    /// Generates a new [`LeftRightArena`], which takes the memory.
+    pub fn new(memory: &'static mut [u8]) -> LeftRightArena {
+        LeftRightArena { memory }
+    }
+
+    /// Places a new buffer holding `data` in the remaining memory.
+    ///
+    /// Returns `None` if the remaining memory is too small, which leaves the memory as it was.
+    pub fn alloc<T: Clone>(&mut self, data: T) -> Option<&'static LeftRightBuffer<T>> {
+        let padding = self
+            .memory
+            .as_mut_ptr()
+            .align_offset(align_of::<LeftRightBuffer<T>>());
+        let needed = padding.checked_add(size_of::<LeftRightBuffer<T>>())?;
+        if needed > self.memory.len() {
+            return None;
+        }
+        let memory = core::mem::take(&mut self.memory);
+        let (slot, rest) = memory.split_at_mut(needed);
+        self.memory = rest;
+        let buffer = slot[padding..].as_mut_ptr().cast::<LeftRightBuffer<T>>();
+        // SAFETY: the slot is aligned, large enough and borrowed for 'static, but never handed out again.
+        unsafe { buffer.write(LeftRightBuffer::new_cloned(data)) };
+        // SAFETY: the buffer got initialized above and only shared references exist from now on.
+        Some(unsafe { &*buffer })
+    }
+
+    /// Returns the number of bytes, which are not used by a buffer yet.
+    ///
+    /// Depending on the alignment, a buffer may need more bytes than its size.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.memory.len()
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+
+    fn memory(len: usize) -> &'static mut [u8] {
+        std::vec![0u8; len].leak()
+    }
+
+    #[test]
+    fn buffers_get_aligned_and_stay_independent() {
+        let mut arena = LeftRightArena::new(memory(
+            2 * (size_of::<LeftRightBuffer<u64>>() + align_of::<LeftRightBuffer<u64>>()),
+        ));
+        // misalign the following buffers on purpose
+        let flag = arena.alloc(true).expect("the memory is large enough");
+        let counter = arena.alloc(0u64).expect("the memory is large enough");
+        let counter_address: *const LeftRightBuffer<u64> = counter;
+        assert!(counter_address.is_aligned(), "the buffer got aligned");
+
+        counter.store(7);
+        flag.store(false);
+        assert_eq!(*counter.read(), 7);
+        assert!(!*flag.read(), "the flag got published");
+    }
+
+    #[test]
+    fn a_full_arena_keeps_its_memory() {
+        // room for the buffer, whatever the alignment of the memory
+        let len = size_of::<LeftRightBuffer<u32>>() + align_of::<LeftRightBuffer<u32>>();
+        let mut arena = LeftRightArena::new(memory(len));
+        assert!(arena.alloc([0u32; 64]).is_none(), "the buffer does not fit");
+        assert_eq!(arena.remaining(), len);
+        assert!(arena.alloc(0u32).is_some(), "the buffer fits");
+        assert!(arena.alloc(0u32).is_none(), "the memory is used up");
+    }
+}
@@ -0,0 +1,323 @@
+use crate::guard::NotSend;
+use crate::ordering;
+use crate::{Violation, fault};
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use core::sync::atomic::{
+    AtomicBool, AtomicI8, AtomicI16, AtomicI32, AtomicU8, AtomicU16, AtomicU32, Ordering, fence,
+};
+
+/// Payloads which fit into a single atomic and can therefore be shared by a [`LeftRightAtomic`].
+pub trait AtomicPayload: Copy {
+    /// The atomic type holding the payload.
+    type Atomic: Sync;
+
+    /// Loads the payload from the atomic.
+    fn load(atomic: &Self::Atomic, order: Ordering) -> Self;
+
+    /// Stores the payload into the atomic.
+    fn store(atomic: &Self::Atomic, value: Self, order: Ordering);
+}
+
+/// Lock-free variant of [`LeftRightBuffer`][crate::LeftRightBuffer] for payloads which fit into a single atomic.
+///
+/// It offers the core of its API, i.e. [`read()`][LeftRightAtomic::read], [`load()`][LeftRightAtomic::load],
+/// [`write()`][LeftRightAtomic::write], [`write_without_sync()`][LeftRightAtomic::write_without_sync],
+/// [`store()`][LeftRightAtomic::store] and [`publish()`][LeftRightAtomic::publish], but the published value lives in a single atomic.
+/// The extensions like `try_read()`, `generation()` or `seal()` are missing.
+/// The write guard owns a local copy of the value, which gets stored by [`publish()`][LeftRightAtomic::publish].
+/// Therefore there are neither locks nor a sync copy involved.
+///
+/// It only needs atomic loads and stores, so it works on targets without compare and swap, e.g. Cortex-M0.
+/// There, a second writer gets detected by a load followed by a store, which misses a second writer interrupting in between.
+///
+/// As there is one constructor per payload type, the payload type has to be named: `LeftRightAtomic::<u32>::new(0)`.
+pub struct LeftRightAtomic<T: AtomicPayload> {
+    value: T::Atomic,
+    writer_active: AtomicBool,
+}
+
+/// Read guard of a [`LeftRightAtomic`] holding a copy of the published value.
+pub struct AtomicReadGuard<T> {
+    value: T,
+}
+
+/// Write guard of a [`LeftRightAtomic`] holding the pending value.
+///
+/// Like [`WriteGuard`][crate::WriteGuard], it is neither `Send` nor `Sync`.
+pub struct AtomicWriteGuard<'a, T: AtomicPayload> {
+    buffer: &'a LeftRightAtomic<T>,
+    value: T,
+    _not_send: NotSend,
+}
+
+macro_rules! impl_atomic_payload {
+    ($($t:ty => $atomic:ty),*) => {
+        $(
+            impl AtomicPayload for $t {
+                type Atomic = $atomic;
+
+                // inlined, so that the panics of invalid orderings get ruled out at the call site
+                #[inline]
+                fn load(atomic: &$atomic, order: Ordering) -> $t {
+                    atomic.load(order)
+                }
+
+                #[inline]
+                fn store(atomic: &$atomic, value: $t, order: Ordering) {
+                    atomic.store(value, order);
+                }
+            }
+
+            impl LeftRightAtomic<$t> {
+                /// Generates a new [`LeftRightAtomic`] and takes the data.
+                pub const fn new(data: $t) -> LeftRightAtomic<$t> {
+                    LeftRightAtomic {
+                        value: <$atomic>::new(data),
+                        writer_active: AtomicBool::new(false),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_atomic_payload!(
+    bool => AtomicBool,
+    u8 => AtomicU8, u16 => AtomicU16, u32 => AtomicU32,
+    i8 => AtomicI8, i16 => AtomicI16, i32 => AtomicI32
+);
+
+/// Lock-free storage of a `u64` on targets without 64 bit atomics, e.g. for timestamp counters.
+///
+/// The value is split into two `AtomicU32` halves, which are double buffered like the sides of a [`LeftRightBuffer`][crate::LeftRightBuffer].
+/// The writer fills the unpublished copy and publishes it by incrementing the generation.
+/// A reader repeats the read if the generation changed meanwhile, so it never observes a torn value and never takes a lock.
+/// With the assumptions, the writer never interrupts a reader, so the read does not get repeated at all.
+///
+/// It requires a single writer, which [`LeftRightAtomic`] guarantees.
+pub struct DoubleBufferedU64 {
+    copies: [[AtomicU32; 2]; 2],
+    generation: AtomicU32,
+}
+
+impl DoubleBufferedU64 {
+    /// Generates a new [`DoubleBufferedU64`] and takes the value.
+    #[must_use]
+    pub const fn new(value: u64) -> DoubleBufferedU64 {
+        let (low, high) = split(value);
+        DoubleBufferedU64 {
+            copies: [
+                [AtomicU32::new(low), AtomicU32::new(high)],
+                [AtomicU32::new(low), AtomicU32::new(high)],
+            ],
+            generation: AtomicU32::new(0),
+        }
+    }
+
+    /// Returns the copy belonging to the generation.
+    fn copy(&self, generation: u32) -> &[AtomicU32; 2] {
+        &self.copies[usize::from(generation & 1 == 1)]
+    }
+}
+
+/// Splits the value into its low and its high half.
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "the truncation is the split"
+)]
+const fn split(value: u64) -> (u32, u32) {
+    (value as u32, (value >> 32) as u32)
+}
+
+impl AtomicPayload for u64 {
+    type Atomic = DoubleBufferedU64;
+
+    fn load(atomic: &DoubleBufferedU64, _order: Ordering) -> u64 {
+        loop {
+            let generation = atomic.generation.load(ordering::ACQUIRE);
+            let [low, high] = atomic.copy(generation);
+            let low = low.load(ordering::RELAXED);
+            let high = high.load(ordering::RELAXED);
+            // orders the loads of the halves before the check of the generation
+            fence(ordering::ACQUIRE);
+            if atomic.generation.load(ordering::RELAXED) == generation {
+                return (u64::from(high) << 32) | u64::from(low);
+            }
+        }
+    }
+
+    fn store(atomic: &DoubleBufferedU64, value: u64, _order: Ordering) {
+        let generation = atomic.generation.load(ordering::RELAXED).wrapping_add(1);
+        let (low, high) = split(value);
+        // orders the last publication before the stores of the halves, which a racing reader detects
+        fence(ordering::RELEASE);
+        atomic.copy(generation)[0].store(low, ordering::RELAXED);
+        atomic.copy(generation)[1].store(high, ordering::RELAXED);
+        atomic.generation.store(generation, ordering::RELEASE);
+    }
+}
+
+impl LeftRightAtomic<u64> {
+    /// Generates a new [`LeftRightAtomic`] and takes the data.
+    #[must_use]
+    pub const fn new(data: u64) -> LeftRightAtomic<u64> {
+        LeftRightAtomic {
+            value: DoubleBufferedU64::new(data),
+            writer_active: AtomicBool::new(false),
+        }
+    }
+}
+
+impl<T: AtomicPayload> LeftRightAtomic<T> {
+    /// Returns a read guard.
+    ///
+    /// The guard holds a copy of the published value, so it never blocks the writer.
+    pub fn read(&self) -> AtomicReadGuard<T> {
+        AtomicReadGuard { value: self.load() }
+    }
+
+    /// Returns a copy of the published value.
+    pub fn load(&self) -> T {
+        T::load(&self.value, ordering::ACQUIRE)
+    }
+
+    /// Publishes `data`.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, otherwise it might panic as this could violate the assumptions.
+    pub fn store(&self, data: T) {
+        let mut writer = self.write_without_sync();
+        *writer = data;
+        self.publish(writer);
+    }
+
+    /// Returns a write guard
+    ///
+    /// The guard starts with the last published value, which is the equivalent of the auto sync of [`LeftRightBuffer::write()`][crate::LeftRightBuffer::write].
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, otherwise it might panic as this could violate the assumptions.
+    pub fn write(&self) -> AtomicWriteGuard<'_, T> {
+        if self.claim_writer() {
+            // wrong usage as there is already a writer.
+            fault::violated(Violation::SecondWriter, None, "write()");
+        }
+        AtomicWriteGuard {
+            buffer: self,
+            value: T::load(&self.value, ordering::RELAXED),
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Returns a write guard
+    ///
+    /// As there is no sync copy involved, this is the same as [`write()`][LeftRightAtomic::write].
+    /// It exists to keep the API of [`LeftRightBuffer`][crate::LeftRightBuffer].
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, otherwise it might panic as this could violate the assumptions.
+    pub fn write_without_sync(&self) -> AtomicWriteGuard<'_, T> {
+        self.write()
+    }
+
+    /// Marks the writer as active and returns `true` if it already was.
+    #[cfg(target_has_atomic = "8")]
+    fn claim_writer(&self) -> bool {
+        self.writer_active.swap(true, ordering::ACQUIRE)
+    }
+
+    /// Marks the writer as active and returns `true` if it already was.
+    ///
+    /// Without compare and swap, a second writer interrupting between the load and the store stays undetected.
+    #[cfg(not(target_has_atomic = "8"))]
+    fn claim_writer(&self) -> bool {
+        let active = self.writer_active.load(ordering::ACQUIRE);
+        self.writer_active.store(true, ordering::RELAXED);
+        active
+    }
+
+    /// Makes the value of the write guard visible to the readers.
+    pub fn publish(&self, writer: AtomicWriteGuard<'_, T>) {
+        T::store(&self.value, writer.value, ordering::RELEASE);
+        drop(writer);
+    }
+}
+
+impl<T> Deref for AtomicReadGuard<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: AtomicPayload> Deref for AtomicWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: AtomicPayload> DerefMut for AtomicWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T: AtomicPayload> Drop for AtomicWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        self.buffer.writer_active.store(false, ordering::RELEASE);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static LR_ATOMIC: LeftRightAtomic<u32> = LeftRightAtomic::<u32>::new(0);
+
+    #[test]
+    fn publish_makes_the_pending_value_visible() {
+        let mut writer = LR_ATOMIC.write();
+        *writer += 7;
+        assert_eq!(*LR_ATOMIC.read(), 0);
+        LR_ATOMIC.publish(writer);
+        assert_eq!(*LR_ATOMIC.read(), 7);
+
+        let writer = LR_ATOMIC.write();
+        assert_eq!(*writer, 7);
+        drop(writer);
+
+        LR_ATOMIC.store(9);
+        assert_eq!(LR_ATOMIC.load(), 9);
+    }
+
+    #[test]
+    fn u64_values_do_not_tear() {
+        let buffer = LeftRightAtomic::<u64>::new(u64::from(u32::MAX));
+        assert_eq!(buffer.load(), 0xffff_ffff);
+        buffer.store(0x1_0000_0000);
+        assert_eq!(buffer.load(), 0x1_0000_0000);
+
+        let mut writer = buffer.write();
+        *writer -= 1;
+        assert_eq!(
+            *buffer.read(),
+            0x1_0000_0000,
+            "the readers keep the published value"
+        );
+        buffer.publish(writer);
+        assert_eq!(buffer.load(), 0xffff_ffff);
+    }
+
+    #[test]
+    #[cfg(not(feature = "panic-free"))] // a violation halts without a handler
+    #[should_panic(expected = "LRBuffer second writer")]
+    fn a_second_writer_panics() {
+        let buffer = LeftRightAtomic::<bool>::new(false);
+        let _writer = buffer.write();
+        let _ = buffer.write_without_sync();
+    }
+}
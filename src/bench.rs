@@ -0,0 +1,115 @@
+//! On-target latency measurements of the `bench-harness` feature, see [`BenchHarness`].
+
+use crate::stats::HoldCells;
+use crate::{HoldStats, LeftRightBuffer, clock};
+
+/// Drives a marker output, e.g. a GPIO pin observed with a logic analyzer. `true` means active.
+pub type Marker = fn(bool);
+
+/// Measures how long a reader gets delayed by a concurrent write or publication on the target.
+///
+/// The writer task generates the load with [`run_writer()`][BenchHarness::run_writer],
+/// while a higher priority reader, e.g. a timer interrupt, calls [`measure_read()`][BenchHarness::measure_read].
+/// As the interrupt hits the writer at varying points, the maximum approaches the worst case.
+///
+/// The time gets measured with the registered [`Clock`][crate::Clock], e.g. wrapping the DWT cycle counter.
+/// Optionally, a [`Marker`] is active while the reader acquires its guard, so that an oscilloscope shows the same.
+pub struct BenchHarness<'a, T> {
+    buffer: &'a LeftRightBuffer<T>,
+    marker: Option<Marker>,
+    latency: HoldCells,
+}
+
+impl<'a, T: Clone> BenchHarness<'a, T> {
+    /// Generates a new [`BenchHarness`] measuring the reads of `buffer`.
+    pub fn new(buffer: &'a LeftRightBuffer<T>) -> BenchHarness<'a, T> {
+        BenchHarness {
+            buffer,
+            marker: None,
+            latency: HoldCells::new(),
+        }
+    }
+
+    /// Attaches a marker, which is active while a measured reader acquires its guard.
+    #[must_use]
+    pub fn with_marker(mut self, marker: Marker) -> BenchHarness<'a, T> {
+        self.marker = Some(marker);
+        self
+    }
+
+    /// Writes and publishes `cycles` times, modifying the pending value with `modify`.
+    ///
+    /// Call it from the writer task as the load, while the reader measures.
+    pub fn run_writer(&self, cycles: u32, mut modify: impl FnMut(&mut T)) {
+        for _ in 0..cycles {
+            let mut writer = self.buffer.write();
+            modify(&mut writer);
+            self.buffer.publish(writer);
+        }
+    }
+
+    /// Reads once and records how long it took to acquire the read guard.
+    ///
+    /// Call it from the reader, e.g. a periodic interrupt preempting [`run_writer()`][BenchHarness::run_writer].
+    pub fn measure_read(&self) {
+        let started = clock::now();
+        if let Some(marker) = self.marker {
+            marker(true);
+        }
+        let guard = self.buffer.read();
+        if let Some(marker) = self.marker {
+            marker(false);
+        }
+        if let (Some(started), Some(now)) = (started, clock::now()) {
+            self.latency.record(now.wrapping_sub(started));
+        }
+        drop(guard);
+    }
+
+    /// Returns the recorded read latencies in ticks of the clock.
+    #[must_use]
+    pub fn latency(&self) -> HoldStats {
+        self.latency.snapshot()
+    }
+
+    /// Discards the recorded read latencies, e.g. after a warm-up.
+    pub fn reset(&self) {
+        self.latency.reset();
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+    use crate::clock::test_clock;
+
+    fn slow_marker(active: bool) {
+        if active {
+            test_clock::advance(5);
+        }
+    }
+
+    #[test]
+    fn read_latencies_get_recorded() {
+        test_clock::register();
+        let buffer = LeftRightBuffer::new(0u32);
+        let harness = BenchHarness::new(&buffer).with_marker(slow_marker);
+        harness.run_writer(3, |value| *value += 1);
+        assert_eq!(*buffer.read(), 3);
+
+        harness.measure_read();
+        harness.measure_read();
+        assert_eq!(
+            harness.latency(),
+            HoldStats {
+                min: 5,
+                max: 5,
+                mean: 5,
+                count: 2
+            }
+        );
+        harness.reset();
+        assert_eq!(harness.latency(), HoldStats::default());
+    }
+}
@@ -0,0 +1,60 @@
+use crate::LeftRightBuffer;
+use core::ops::{BitAnd, BitOr, BitXor, Not};
+
+impl<T> LeftRightBuffer<T>
+where
+    T: Copy + BitAnd<Output = T> + BitOr<Output = T> + BitXor<Output = T> + Not<Output = T>,
+{
+    /// Sets the bits of `mask` in the last written value and publishes the result.
+    ///
+    /// Returns the published value.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, see [`write()`][LeftRightBuffer::write].
+    pub fn set_bits(&self, mask: T) -> T {
+        self.modify_bits(|value| value | mask)
+    }
+
+    /// Clears the bits of `mask` in the last written value and publishes the result.
+    ///
+    /// Returns the published value.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, see [`write()`][LeftRightBuffer::write].
+    pub fn clear_bits(&self, mask: T) -> T {
+        self.modify_bits(|value| value & !mask)
+    }
+
+    /// Toggles the bits of `mask` in the last written value and publishes the result.
+    ///
+    /// Returns the published value.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, see [`write()`][LeftRightBuffer::write].
+    pub fn toggle_bits(&self, mask: T) -> T {
+        self.modify_bits(|value| value ^ mask)
+    }
+
+    fn modify_bits(&self, f: impl FnOnce(T) -> T) -> T {
+        let mut writer = self.write();
+        *writer = f(*writer);
+        let value = *writer;
+        self.publish(writer);
+        value
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use crate::LeftRightBuffer;
+
+    #[test]
+    fn bit_helpers_build_on_the_last_written_value() {
+        let status = LeftRightBuffer::new(0b0000_0001u8);
+        assert_eq!(status.set_bits(0b0000_0110), 0b0000_0111);
+        assert_eq!(status.clear_bits(0b0000_0001), 0b0000_0110);
+        assert_eq!(status.toggle_bits(0b0000_1010), 0b0000_1100);
+        assert_eq!(*status.read(), 0b0000_1100);
+    }
+}
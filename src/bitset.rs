@@ -0,0 +1,219 @@
+//! Flag sets, see [`LeftRightBitSet`].
+
+use crate::{LeftRightBuffer, ReadGuard};
+
+/// Set of up to `BITS` flags, e.g. fault flags or channel enables, which are numbered from 0.
+///
+/// The flags are stored in a `u128`, so `BITS` is at most 128.
+#[cfg_attr(any(test, not(feature = "fmt-free")), derive(Debug))]
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub struct BitSet<const BITS: usize> {
+    bits: u128,
+}
+
+impl<const BITS: usize> BitSet<BITS> {
+    /// Returns the mask of flag `index`, or `None` if it is out of range.
+    fn mask(index: usize) -> Option<u128> {
+        if index >= BITS {
+            return None;
+        }
+        1u128.checked_shl(u32::try_from(index).ok()?)
+    }
+
+    /// Sets flag `index`.
+    ///
+    /// Returns `false` if `index` is out of range.
+    pub fn set(&mut self, index: usize) -> bool {
+        let Some(mask) = Self::mask(index) else {
+            return false;
+        };
+        self.bits |= mask;
+        true
+    }
+
+    /// Clears flag `index`.
+    ///
+    /// Returns `false` if `index` is out of range.
+    pub fn clear(&mut self, index: usize) -> bool {
+        let Some(mask) = Self::mask(index) else {
+            return false;
+        };
+        self.bits &= !mask;
+        true
+    }
+
+    /// Clears all flags.
+    pub fn clear_all(&mut self) {
+        self.bits = 0;
+    }
+
+    /// Returns `true` if flag `index` is set.
+    #[must_use]
+    pub fn contains(&self, index: usize) -> bool {
+        Self::mask(index).is_some_and(|mask| self.bits & mask != 0)
+    }
+
+    /// Returns the numbers of the set flags in ascending order.
+    pub fn iter_set(&self) -> impl Iterator<Item = usize> {
+        let bits = self.bits;
+        (0..BITS).filter(move |&index| {
+            u32::try_from(index)
+                .ok()
+                .and_then(|shift| bits.checked_shr(shift))
+                .is_some_and(|shifted| shifted & 1 != 0)
+        })
+    }
+
+    /// Returns the number of set flags.
+    #[must_use]
+    pub const fn count(&self) -> u32 {
+        self.bits.count_ones()
+    }
+
+    /// Returns `true` if no flag is set.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// Returns the flags as a mask, whose bit `i` is flag `i`.
+    #[must_use]
+    pub const fn bits(&self) -> u128 {
+        self.bits
+    }
+}
+
+/// Buffer of a set of up to `BITS` flags, e.g. a fault flag matrix or the channel enable mask.
+///
+/// The writer sets and clears single flags with [`set()`][LeftRightBitSet::set] and [`clear()`][LeftRightBitSet::clear],
+/// which stay invisible until [`publish()`][LeftRightBitSet::publish] publishes the whole set.
+///
+/// ```
+/// use minimal_left_right::LeftRightBitSet;
+///
+/// static FAULTS: LeftRightBitSet<96> = LeftRightBitSet::new();
+///
+/// FAULTS.set(3);
+/// FAULTS.set(70);
+/// FAULTS.publish();
+/// assert!(FAULTS.load().iter_set().eq([3, 70]));
+/// ```
+pub struct LeftRightBitSet<const BITS: usize> {
+    buffer: LeftRightBuffer<BitSet<BITS>>,
+}
+
+impl<const BITS: usize> LeftRightBitSet<BITS> {
+    /// Generates a new [`LeftRightBitSet`] without any set flag.
+    ///
+    /// It does not compile if `BITS` is more than 128.
+    #[cfg(not(any(loom, shuttle)))]
+    #[must_use]
+    pub const fn new() -> LeftRightBitSet<BITS> {
+        const { assert!(BITS <= 128, "a LeftRightBitSet holds at most 128 flags") };
+        LeftRightBitSet {
+            buffer: LeftRightBuffer::new(BitSet { bits: 0 }),
+        }
+    }
+
+    /// Generates a new [`LeftRightBitSet`] without any set flag.
+    #[cfg(any(loom, shuttle))]
+    #[must_use]
+    pub fn new() -> LeftRightBitSet<BITS> {
+        const { assert!(BITS <= 128, "a LeftRightBitSet holds at most 128 flags") };
+        LeftRightBitSet {
+            buffer: LeftRightBuffer::new(BitSet { bits: 0 }),
+        }
+    }
+
+    /// Sets flag `index` in the pending set.
+    ///
+    /// Returns `false` if `index` is out of range.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, otherwise it might panic as this could violate the assumptions.
+    pub fn set(&self, index: usize) -> bool {
+        self.buffer.write().set(index)
+    }
+
+    /// Clears flag `index` in the pending set.
+    ///
+    /// Returns `false` if `index` is out of range.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, otherwise it might panic as this could violate the assumptions.
+    pub fn clear(&self, index: usize) -> bool {
+        self.buffer.write().clear(index)
+    }
+
+    /// Publishes the pending set with all changes since the last publication.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, otherwise it might panic as this could violate the assumptions.
+    pub fn publish(&self) {
+        let pending = self.buffer.write();
+        self.buffer.publish(pending);
+    }
+
+    /// Returns `true` if flag `index` is set in the published set.
+    #[must_use]
+    pub fn contains(&self, index: usize) -> bool {
+        self.buffer.read().contains(index)
+    }
+
+    /// Returns a read guard of the published set.
+    #[must_use]
+    pub fn read(&self) -> ReadGuard<'_, BitSet<BITS>> {
+        self.buffer.read()
+    }
+
+    /// Returns a copy of the published set.
+    #[must_use]
+    pub fn load(&self) -> BitSet<BITS> {
+        self.buffer.load()
+    }
+}
+
+impl<const BITS: usize> Default for LeftRightBitSet<BITS> {
+    fn default() -> LeftRightBitSet<BITS> {
+        LeftRightBitSet::new()
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_flags_get_published_as_a_whole() {
+        let enables = LeftRightBitSet::<128>::new();
+        assert!(enables.set(0));
+        assert!(enables.set(127));
+        assert!(!enables.set(128), "out of range");
+        assert!(!enables.contains(0), "the flags are pending");
+        enables.publish();
+        assert!(enables.contains(127));
+        assert_eq!(enables.read().count(), 2);
+
+        assert!(enables.clear(0));
+        assert!(enables.set(64));
+        enables.publish();
+        let published = enables.load();
+        assert!(published.iter_set().eq([64, 127]));
+        assert_eq!(published.bits(), (1 << 127) | (1 << 64));
+    }
+
+    #[test]
+    fn the_bit_set_edits_single_flags() {
+        let mut flags = BitSet::<10>::default();
+        assert!(flags.is_empty());
+        assert!(flags.set(9));
+        assert!(!flags.set(10), "out of range");
+        assert!(!flags.contains(10));
+        assert!(flags.clear(9));
+        assert!(!flags.clear(10), "out of range");
+        assert!(flags.set(2));
+        flags.clear_all();
+        assert!(flags.is_empty());
+    }
+}
@@ -0,0 +1,137 @@
+//! Double-buffered audio and DSP blocks, see [`BlockBuffer`].
+
+use crate::{LeftRightBuffer, ReadGuard, WriteGuard};
+
+/// Block of `FRAMES` frames with one sample of type `S` per channel, interleaved like the I2S stream.
+#[cfg_attr(any(test, not(feature = "fmt-free")), derive(Debug))]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Block<S, const FRAMES: usize, const CHANNELS: usize> {
+    frames: [[S; CHANNELS]; FRAMES],
+}
+
+impl<S, const FRAMES: usize, const CHANNELS: usize> Block<S, FRAMES, CHANNELS> {
+    /// Returns the frames of the block.
+    #[must_use]
+    pub fn frames(&self) -> &[[S; CHANNELS]; FRAMES] {
+        &self.frames
+    }
+
+    /// Returns the frames of the block for writing.
+    pub fn frames_mut(&mut self) -> &mut [[S; CHANNELS]; FRAMES] {
+        &mut self.frames
+    }
+
+    /// Returns the interleaved samples of all channels, e.g. as the source of a DMA transfer.
+    #[must_use]
+    pub fn samples(&self) -> &[S] {
+        self.frames.as_flattened()
+    }
+
+    /// Returns the samples of one channel, which are empty if the `channel` is out of range.
+    pub fn channel(&self, channel: usize) -> impl Iterator<Item = &S> {
+        self.frames
+            .iter()
+            .filter_map(move |frame| frame.get(channel))
+    }
+
+    /// Returns the samples of one channel for writing, which are empty if the `channel` is out of range.
+    pub fn channel_mut(&mut self, channel: usize) -> impl Iterator<Item = &mut S> {
+        self.frames
+            .iter_mut()
+            .filter_map(move |frame| frame.get_mut(channel))
+    }
+}
+
+/// Buffer of audio or DSP blocks, whose writer fills a whole block, which the publication hands to the consumer at once.
+///
+/// The consumer, e.g. the interrupt feeding the I2S DMA, always gets a complete block, while the writer fills the next one.
+/// As the writer replaces the whole block, the write skips the sync copy.
+///
+/// ```
+/// use minimal_left_right::BlockBuffer;
+///
+/// static OUTPUT: BlockBuffer<i16, 4, 2> = BlockBuffer::new(0);
+///
+/// let mut block = OUTPUT.write_block();
+/// for (frame, sample) in block.frames_mut().iter_mut().zip([1, 2, 3, 4]) {
+///     *frame = [sample, -sample];
+/// }
+/// OUTPUT.publish(block);
+/// assert!(OUTPUT.read_block().channel(1).copied().eq([-1, -2, -3, -4]));
+/// ```
+pub struct BlockBuffer<S, const FRAMES: usize, const CHANNELS: usize> {
+    buffer: LeftRightBuffer<Block<S, FRAMES, CHANNELS>>,
+}
+
+impl<S: Copy, const FRAMES: usize, const CHANNELS: usize> BlockBuffer<S, FRAMES, CHANNELS> {
+    /// Generates a new [`BlockBuffer`], whose blocks start with the `silence` in every sample.
+    #[cfg(not(any(loom, shuttle)))]
+    pub const fn new(silence: S) -> BlockBuffer<S, FRAMES, CHANNELS> {
+        BlockBuffer {
+            buffer: LeftRightBuffer::new(Block {
+                frames: [[silence; CHANNELS]; FRAMES],
+            }),
+        }
+    }
+
+    /// Generates a new [`BlockBuffer`], whose blocks start with the `silence` in every sample.
+    #[cfg(any(loom, shuttle))]
+    pub fn new(silence: S) -> BlockBuffer<S, FRAMES, CHANNELS> {
+        BlockBuffer {
+            buffer: LeftRightBuffer::new(Block {
+                frames: [[silence; CHANNELS]; FRAMES],
+            }),
+        }
+    }
+
+    /// Returns a write guard of the next block, which still holds the samples of an older block.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, otherwise it might panic as this could violate the assumptions.
+    pub fn write_block(&self) -> WriteGuard<'_, Block<S, FRAMES, CHANNELS>> {
+        self.buffer.write_without_sync()
+    }
+
+    /// Hands the filled block to the consumer, see [`LeftRightBuffer::publish()`].
+    ///
+    /// # Panics
+    /// The guard belongs to another buffer or to a block, which got published meanwhile, see [`Violation::ForeignGuard`][crate::Violation::ForeignGuard].
+    pub fn publish(&self, block: WriteGuard<'_, Block<S, FRAMES, CHANNELS>>) {
+        self.buffer.publish(block);
+    }
+
+    /// Returns a read guard of the last complete block.
+    #[must_use]
+    pub fn read_block(&self) -> ReadGuard<'_, Block<S, FRAMES, CHANNELS>> {
+        self.buffer.read()
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_consumer_gets_complete_blocks() {
+        let output = BlockBuffer::<i16, 3, 2>::new(0);
+        let mut block = output.write_block();
+        block
+            .channel_mut(0)
+            .zip([1, 2, 3])
+            .for_each(|(sample, value)| *sample = value);
+        block.channel_mut(1).for_each(|sample| *sample = -1);
+        assert_eq!(block.channel_mut(2).count(), 0, "there is no third channel");
+        assert_eq!(
+            output.read_block().samples(),
+            [0; 6],
+            "the block is pending"
+        );
+
+        output.publish(block);
+        let published = output.read_block();
+        assert_eq!(published.samples(), [1, -1, 2, -1, 3, -1]);
+        assert!(published.channel(0).copied().eq([1, 2, 3]));
+        assert_eq!(published.frames()[2], [3, -1]);
+    }
+}
@@ -0,0 +1,110 @@
+use crate::{Counter, LeftRightBuffer, WriteGuard};
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+
+/// Invariant lifetime, which is unique per call of [`branded()`][LeftRightBuffer::branded].
+type Brand<'id> = PhantomData<fn(&'id ()) -> &'id ()>;
+
+/// View of a [`LeftRightBuffer`] with a unique brand, see [`branded()`][LeftRightBuffer::branded].
+///
+/// Its write guards carry the brand, so publishing the guard of another buffer fails to compile:
+///
+/// ```compile_fail
+/// use minimal_left_right::LeftRightBuffer;
+///
+/// let speed = LeftRightBuffer::new(0u32);
+/// let torque = LeftRightBuffer::new(0u32);
+/// speed.branded(|speed| {
+///     torque.branded(|torque| {
+///         let writer = torque.write();
+///         speed.publish(writer);
+///     });
+/// });
+/// ```
+///
+/// Otherwise, it dereferences to the [`LeftRightBuffer`]:
+///
+/// ```
+/// use minimal_left_right::LeftRightBuffer;
+///
+/// let speed = LeftRightBuffer::new(0u32);
+/// speed.branded(|speed| {
+///     let mut writer = speed.write();
+///     *writer = 7;
+///     speed.publish(writer);
+///     assert_eq!(*speed.read(), 7);
+/// });
+/// ```
+pub struct Branded<'a, 'id, T, G: Counter = u32> {
+    buffer: &'a LeftRightBuffer<T, G>,
+    brand: Brand<'id>,
+}
+
+/// Write guard of a [`Branded`] buffer, which can only be published to the same buffer.
+pub struct BrandedWriteGuard<'a, 'id, T> {
+    guard: WriteGuard<'a, T>,
+    _brand: Brand<'id>,
+}
+
+impl<'a, T: Clone, G: Counter> Branded<'a, '_, T, G> {
+    pub(crate) fn new(buffer: &'a LeftRightBuffer<T, G>) -> Self {
+        Branded {
+            buffer,
+            brand: PhantomData,
+        }
+    }
+}
+
+impl<'a, 'id, T: Clone, G: Counter> Branded<'a, 'id, T, G> {
+    /// Returns a branded write guard, see [`LeftRightBuffer::write()`].
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, otherwise it might panic as this could violate the assumptions.
+    #[must_use]
+    pub fn write(&self) -> BrandedWriteGuard<'a, 'id, T> {
+        self.brand(self.buffer.write())
+    }
+
+    /// Returns a branded write guard, see [`LeftRightBuffer::write_without_sync()`].
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, otherwise it might panic as this could violate the assumptions.
+    #[must_use]
+    pub fn write_without_sync(&self) -> BrandedWriteGuard<'a, 'id, T> {
+        self.brand(self.buffer.write_without_sync())
+    }
+
+    /// Makes the value of the branded write guard visible to the readers, see [`LeftRightBuffer::publish()`].
+    pub fn publish(&self, writer: BrandedWriteGuard<'_, 'id, T>) {
+        self.buffer.publish(writer.guard);
+    }
+
+    fn brand(&self, guard: WriteGuard<'a, T>) -> BrandedWriteGuard<'a, 'id, T> {
+        BrandedWriteGuard {
+            guard,
+            _brand: self.brand,
+        }
+    }
+}
+
+impl<T, G: Counter> Deref for Branded<'_, '_, T, G> {
+    type Target = LeftRightBuffer<T, G>;
+
+    fn deref(&self) -> &LeftRightBuffer<T, G> {
+        self.buffer
+    }
+}
+
+impl<T> Deref for BrandedWriteGuard<'_, '_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<T> DerefMut for BrandedWriteGuard<'_, '_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.guard
+    }
+}
@@ -0,0 +1,123 @@
+//! Distribution of one value to several consumers with their own buffers, see [`Broadcast`].
+
+use crate::{Generation, LeftRightBuffer, ReadGuard};
+
+/// One writer publishing to `N` consumers, each of which reads from its own [`LeftRightBuffer`].
+///
+/// A consumer holding its read guard for long only affects its own buffer, so the others are never perturbed.
+/// This suits consumers running at very different priorities.
+///
+/// ```
+/// use minimal_left_right::Broadcast;
+///
+/// let speed = Broadcast::<u16, 2>::new(0);
+/// let [display, logger] = [0, 1].map(|index| speed.consumer(index).expect("in range"));
+/// assert_eq!(speed.publish(&50), 2);
+/// assert_eq!(*display.read(), 50);
+/// assert_eq!(*logger.read(), 50);
+/// ```
+pub struct Broadcast<T, const N: usize> {
+    consumers: [LeftRightBuffer<T>; N],
+}
+
+/// Read access of one consumer of a [`Broadcast`].
+pub struct BroadcastReader<'a, T> {
+    buffer: &'a LeftRightBuffer<T>,
+}
+
+impl<T: Clone, const N: usize> Broadcast<T, N> {
+    /// Generates a new [`Broadcast`], whose consumers start with the data.
+    pub fn new(data: T) -> Broadcast<T, N> {
+        Broadcast {
+            consumers: core::array::from_fn(|_| LeftRightBuffer::new_cloned(data.clone())),
+        }
+    }
+
+    /// Returns the read access of the consumer with the given `index`, or `None` if it is out of range.
+    pub fn consumer(&self, index: usize) -> Option<BroadcastReader<'_, T>> {
+        self.consumers
+            .get(index)
+            .map(|buffer| BroadcastReader { buffer })
+    }
+
+    /// Returns the buffer of the consumer with the given `index`, which must be in range.
+    #[cfg(feature = "per-core")]
+    pub(crate) fn replica(&self, index: usize) -> &LeftRightBuffer<T> {
+        &self.consumers[index]
+    }
+
+    /// Copies `data` into the buffer of every consumer and publishes it there.
+    ///
+    /// A consumer, which still holds a read guard of its previous value, violates the assumptions.
+    /// Instead of affecting the others, it misses this value and gets the next one.
+    /// Returns the number of consumers, which got the value.
+    ///
+    /// Call it only from the writer, as it is the only writer of all buffers.
+    pub fn publish(&self, data: &T) -> usize {
+        self.consumers
+            .iter()
+            .filter(|buffer| {
+                let Ok(mut writer) = buffer.try_write_without_sync() else {
+                    return false;
+                };
+                writer.clone_from(data);
+                buffer.publish(writer);
+                true
+            })
+            .count()
+    }
+}
+
+impl<T: Clone> BroadcastReader<'_, T> {
+    /// Returns a read guard of the last value this consumer got, see [`read()`][LeftRightBuffer::read].
+    #[must_use]
+    pub fn read(&self) -> ReadGuard<'_, T> {
+        self.buffer.read()
+    }
+
+    /// Returns a read guard, if it is available without waiting, see [`try_read()`][LeftRightBuffer::try_read].
+    #[must_use]
+    pub fn try_read(&self) -> Option<ReadGuard<'_, T>> {
+        self.buffer.try_read()
+    }
+
+    /// Returns the number of values this consumer got, see [`generation()`][LeftRightBuffer::generation].
+    #[must_use]
+    pub fn generation(&self) -> Generation {
+        self.buffer.generation()
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_slow_consumer_only_misses_its_own_values() {
+        let broadcast = Broadcast::<u8, 3>::new(0);
+        let fast = broadcast.consumer(0).expect("in range");
+        let slow = broadcast.consumer(1).expect("in range");
+        assert!(
+            broadcast.consumer(3).is_none(),
+            "there are only 3 consumers"
+        );
+
+        let held = slow.read();
+        assert_eq!(broadcast.publish(&1), 3, "the first value reaches everyone");
+        assert_eq!(
+            broadcast.publish(&2),
+            2,
+            "the slow consumer holds its pending side"
+        );
+        assert_eq!(*fast.read(), 2);
+        assert_eq!(*held, 0);
+        assert_eq!(*slow.read(), 1);
+        drop(held);
+
+        assert_eq!(broadcast.publish(&3), 3, "the slow consumer caught up");
+        assert_eq!(*slow.read(), 3);
+        assert_eq!(slow.generation(), 2);
+        assert_eq!(fast.generation(), 3);
+    }
+}
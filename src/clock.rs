@@ -0,0 +1,130 @@
+//! Time source of the time-based features.
+
+/// Source of the time, e.g. a hardware timer or the tick counter of an RTOS.
+///
+/// The ticks wrap around, so durations get calculated with `now.wrapping_sub(then)`.
+/// Register it once with [`set_clock()`].
+pub trait Clock: Sync {
+    /// Returns the current time in ticks.
+    fn now(&self) -> u32;
+}
+
+static CLOCK: spin::Once<&'static dyn Clock> = spin::Once::new();
+
+/// Registers the clock, which gets used by the time-based features.
+///
+/// The clock can only be registered once. Returns `false` if there was already a clock.
+/// Without a clock, no time gets recorded.
+pub fn set_clock(clock: &'static dyn Clock) -> bool {
+    let mut registered = false;
+    CLOCK.call_once(|| {
+        registered = true;
+        clock
+    });
+    registered
+}
+
+/// Returns the current time of the registered clock.
+#[inline]
+pub(crate) fn now() -> Option<u32> {
+    CLOCK.get().map(|clock| clock.now())
+}
+
+/// [`Clock`] of the `std` backend counting microseconds with [`std::time::Instant`], e.g. for simulators on the host.
+///
+/// The time starts at the first call and wraps around after about 71 minutes. Register it with `set_clock(&StdClock)`.
+///
+/// It is missing on `wasm32-unknown-unknown`, whose [`std::time::Instant`] panics. Register a clock of the host there,
+/// e.g. one backed by `performance.now()`.
+#[cfg(feature = "std")]
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub struct StdClock;
+
+#[cfg(feature = "std")]
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+impl StdClock {
+    /// Number of ticks per second.
+    pub const TICKS_PER_SECOND: u32 = 1_000_000;
+
+    /// Returns the number of ticks of `duration`, saturating at the maximum.
+    pub(crate) fn ticks(duration: std::time::Duration) -> u32 {
+        u32::try_from(duration.as_micros()).unwrap_or(u32::MAX)
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+impl Clock for StdClock {
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "the ticks wrap around like every clock"
+    )]
+    fn now(&self) -> u32 {
+        static EPOCH: std::sync::OnceLock<std::time::Instant> = std::sync::OnceLock::new();
+        EPOCH
+            .get_or_init(std::time::Instant::now)
+            .elapsed()
+            .as_micros() as u32
+    }
+}
+
+/// Clock of the unit tests, which only advances on request of the current test.
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+pub(crate) mod test_clock {
+    use super::Clock;
+    use core::cell::Cell;
+
+    std::thread_local! {
+        static NOW: Cell<u32> = const { Cell::new(0) };
+    }
+
+    struct ThreadClock;
+
+    impl Clock for ThreadClock {
+        fn now(&self) -> u32 {
+            NOW.with(Cell::get)
+        }
+    }
+
+    /// Registers the test clock, if not done yet by another test.
+    pub(crate) fn register() {
+        super::set_clock(&ThreadClock);
+    }
+
+    /// Advances the time of the current test by `ticks`.
+    pub(crate) fn advance(ticks: u32) {
+        NOW.with(|now| now.set(now.get().wrapping_add(ticks)));
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_registered_clock_gets_used() {
+        test_clock::register();
+        let start = now().expect("a clock is registered");
+        test_clock::advance(u32::MAX);
+        assert_eq!(now().map(|now| now.wrapping_sub(start)), Some(u32::MAX));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn the_std_clock_counts_microseconds() {
+        let start = StdClock.now();
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        assert!(
+            StdClock.now().wrapping_sub(start) >= 2_000,
+            "the time advanced"
+        );
+        assert_eq!(StdClock::ticks(std::time::Duration::from_millis(3)), 3_000);
+        assert_eq!(
+            StdClock::ticks(std::time::Duration::MAX),
+            u32::MAX,
+            "the ticks saturate"
+        );
+    }
+}
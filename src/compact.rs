@@ -0,0 +1,303 @@
+//! Buffers with both sides behind a single control word, see [`CompactBuffer`].
+
+use crate::guard::NotSend;
+use crate::sync::AtomicU32;
+use crate::{Error, Side, Violation, fault, ordering};
+use core::cell::UnsafeCell;
+use core::ops::{Deref, DerefMut};
+
+// Bit positions within the control word.
+// DIRECTION set means reading happens on right and writing on the left, like the state of a LeftRightBuffer
+const DIRECTION: u32 = 1 << 0;
+// WRITER set means the writer holds the pending side
+const WRITER: u32 = 1 << 1;
+// The readers of the left and of the right side are counted in 15 bits each, starting at these bits.
+const LEFT_READERS: u32 = 2;
+const RIGHT_READERS: u32 = 17;
+const MAX_READERS: u32 = (1 << 15) - 1;
+
+/// Buffer storing both sides as `[T; 2]` behind a single `u32` control word, e.g. for many buffers of small payloads.
+///
+/// A [`LeftRightBuffer`][crate::LeftRightBuffer] locks each side with its own lock and keeps further control state,
+/// like the generation and the event flags. Here, the direction, the writer and the readers of both sides share one control word,
+/// which shrinks the overhead per buffer to 4 bytes plus the padding. It offers the core API only and every [`write()`][CompactBuffer::write] syncs the pending side.
+///
+/// ```
+/// use minimal_left_right::CompactBuffer;
+///
+/// static LIMIT: CompactBuffer<u16> = CompactBuffer::new(100);
+///
+/// let mut writer = LIMIT.write();
+/// *writer += 20;
+/// LIMIT.publish(writer);
+/// assert_eq!(*LIMIT.read(), 120);
+/// ```
+pub struct CompactBuffer<T> {
+    sides: [UnsafeCell<T>; 2],
+    control: AtomicU32,
+}
+
+// SAFETY: the control word hands out shared references to the published side and one mutable reference to the pending side,
+// only if nobody reads it, like the RwLock of each side of a LeftRightBuffer.
+unsafe impl<T: Send + Sync> Sync for CompactBuffer<T> {}
+
+/// Read guard of a [`CompactBuffer`], see [`CompactBuffer::read()`].
+pub struct CompactReadGuard<'a, T> {
+    buffer: &'a CompactBuffer<T>,
+    side: Side,
+}
+
+/// Write guard of a [`CompactBuffer`], see [`CompactBuffer::write()`].
+///
+/// Like the [`WriteGuard`][crate::WriteGuard], it is neither `Send` nor `Sync`.
+pub struct CompactWriteGuard<'a, T> {
+    buffer: &'a CompactBuffer<T>,
+    side: Side,
+    _not_send: NotSend,
+}
+
+/// Returns the side, which serves the readers.
+const fn read_side(control: u32) -> Side {
+    if control & DIRECTION == 0 {
+        Side::Left
+    } else {
+        Side::Right
+    }
+}
+
+/// Returns the first bit of the reader counter of `side`.
+const fn readers_shift(side: Side) -> u32 {
+    match side {
+        Side::Left => LEFT_READERS,
+        Side::Right => RIGHT_READERS,
+    }
+}
+
+/// Returns the number of readers of `side`.
+const fn readers(control: u32, side: Side) -> u32 {
+    (control >> readers_shift(side)) & MAX_READERS
+}
+
+impl<T: Copy> CompactBuffer<T> {
+    /// Generates a new [`CompactBuffer`] holding `data` on both sides.
+    pub const fn new(data: T) -> CompactBuffer<T> {
+        CompactBuffer {
+            sides: [UnsafeCell::new(data), UnsafeCell::new(data)],
+            control: AtomicU32::new(0),
+        }
+    }
+}
+
+impl<T: Clone> CompactBuffer<T> {
+    /// Generates a new [`CompactBuffer`] holding clones of `data` on both sides, for payloads which are not `Copy`.
+    pub fn new_cloned(data: T) -> CompactBuffer<T> {
+        CompactBuffer {
+            sides: [UnsafeCell::new(data.clone()), UnsafeCell::new(data)],
+            control: AtomicU32::new(0),
+        }
+    }
+
+    /// Returns a read guard of the published value.
+    ///
+    /// The side and its reader get picked in one step, so a racing publication never strands the reader on the pending side.
+    #[must_use]
+    pub fn read(&self) -> CompactReadGuard<'_, T> {
+        let mut control = self.control.load(ordering::RELAXED);
+        loop {
+            let side = read_side(control);
+            if readers(control, side) == MAX_READERS {
+                core::hint::spin_loop();
+                control = self.control.load(ordering::RELAXED);
+                continue;
+            }
+            match self.control.compare_exchange_weak(
+                control,
+                control + (1 << readers_shift(side)),
+                ordering::ACQUIRE,
+                ordering::RELAXED,
+            ) {
+                Ok(_) => return CompactReadGuard { buffer: self, side },
+                Err(current) => control = current,
+            }
+        }
+    }
+
+    /// Returns a copy of the published value.
+    pub fn load(&self) -> T {
+        self.read().clone()
+    }
+
+    /// Returns a write guard of the pending side, which starts with the published value.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, otherwise it might panic as this could violate the assumptions.
+    pub fn write(&self) -> CompactWriteGuard<'_, T> {
+        let mut writer = match self.writer() {
+            Ok(writer) => writer,
+            Err(violation) => fault::violated(violation, None, "write()"),
+        };
+        writer.clone_from(&self.read());
+        writer
+    }
+
+    /// Returns a write guard of the pending side, which starts with the published value.
+    ///
+    /// # Errors
+    /// [`Violation::Write`] if a reader or another writer holds the pending side.
+    pub fn try_write(&self) -> Result<CompactWriteGuard<'_, T>, Error> {
+        let mut writer = self.writer().map_err(Error::Violation)?;
+        writer.clone_from(&self.read());
+        Ok(writer)
+    }
+
+    /// Replaces the pending value with `data` and publishes it.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, otherwise it might panic as this could violate the assumptions.
+    pub fn store(&self, data: T) {
+        let mut writer = match self.writer() {
+            Ok(writer) => writer,
+            Err(error) => fault::violated(error, None, "store()"),
+        };
+        *writer = data;
+        self.publish(writer);
+    }
+
+    /// Publishes the written value.
+    ///
+    /// # Panics
+    /// The guard belongs to another buffer, see [`Violation::ForeignGuard`].
+    pub fn publish(&self, writer: CompactWriteGuard<'_, T>) {
+        if !core::ptr::eq(writer.buffer, self) {
+            fault::violated(Violation::ForeignGuard, None, "publish()");
+        }
+        self.control.fetch_xor(DIRECTION, ordering::RELEASE);
+        drop(writer);
+    }
+
+    /// Locks the pending side for the writer.
+    fn writer(&self) -> Result<CompactWriteGuard<'_, T>, Violation> {
+        let control = self.control.load(ordering::RELAXED);
+        let side = match read_side(control) {
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        };
+        if control & WRITER != 0 || readers(control, side) != 0 {
+            return Err(Violation::Write(side));
+        }
+        self.control
+            .compare_exchange(
+                control,
+                control | WRITER,
+                ordering::ACQUIRE,
+                ordering::RELAXED,
+            )
+            .map_err(|_| Violation::Write(side))?;
+        Ok(CompactWriteGuard {
+            buffer: self,
+            side,
+            _not_send: NotSend::default(),
+        })
+    }
+}
+
+impl<T> CompactBuffer<T> {
+    /// Returns the number of bytes of this instantiation, i.e. both sides and the control word, see [`LeftRightBuffer::footprint()`][crate::LeftRightBuffer::footprint].
+    #[must_use]
+    pub const fn footprint() -> usize {
+        size_of::<CompactBuffer<T>>()
+    }
+
+    /// Returns the cell of `side`.
+    fn side(&self, side: Side) -> &UnsafeCell<T> {
+        let [left, right] = &self.sides;
+        match side {
+            Side::Left => left,
+            Side::Right => right,
+        }
+    }
+}
+
+impl<T> Deref for CompactReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: the reader counter of the side keeps the writer away, as long as the guard lives.
+        unsafe { &*self.buffer.side(self.side).get() }
+    }
+}
+
+impl<T> Drop for CompactReadGuard<'_, T> {
+    fn drop(&mut self) {
+        self.buffer
+            .control
+            .fetch_sub(1 << readers_shift(self.side), ordering::RELEASE);
+    }
+}
+
+impl<T> Deref for CompactWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: the writer bit keeps other writers away and the readers only enter the published side.
+        unsafe { &*self.buffer.side(self.side).get() }
+    }
+}
+
+impl<T> DerefMut for CompactWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: the writer bit keeps other writers away and the readers only enter the published side.
+        unsafe { &mut *self.buffer.side(self.side).get() }
+    }
+}
+
+impl<T> Drop for CompactWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        self.buffer.control.fetch_and(!WRITER, ordering::RELEASE);
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+    use crate::LeftRightBuffer;
+
+    #[test]
+    fn the_control_word_guards_both_sides() {
+        let buffer = CompactBuffer::new(1u8);
+        let held = buffer.read();
+        let mut writer = buffer.write();
+        assert_eq!(*writer, 1, "the writer starts with the published value");
+        *writer = 2;
+        assert!(
+            matches!(
+                buffer.try_write(),
+                Err(Error::Violation(Violation::Write(Side::Right)))
+            ),
+            "there is already a writer"
+        );
+        buffer.publish(writer);
+        assert_eq!(*held, 1, "the reader keeps the retired side");
+        assert_eq!(*buffer.read(), 2);
+        assert!(
+            matches!(
+                buffer.try_write(),
+                Err(Error::Violation(Violation::Write(Side::Left)))
+            ),
+            "the reader holds the pending side"
+        );
+        drop(held);
+        buffer.store(3);
+        assert_eq!(buffer.load(), 3);
+    }
+
+    #[test]
+    fn the_overhead_is_a_single_word() {
+        assert_eq!(CompactBuffer::<u32>::footprint(), 3 * size_of::<u32>());
+        assert!(
+            2 * size_of::<CompactBuffer<u32>>() <= size_of::<LeftRightBuffer<u32>>(),
+            "the overhead shrinks at least by half"
+        );
+    }
+}
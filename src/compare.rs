@@ -0,0 +1,43 @@
+use crate::{LeftRightBuffer, WriteGuard};
+
+impl<T: Clone + PartialEq> LeftRightBuffer<T> {
+    /// Returns a write guard
+    ///
+    /// Same as [`write()`][LeftRightBuffer::write], but the sync compares both sides first and only copies the data if they differ.
+    /// Use this function for large payloads which get written often but change rarely, as a comparison is cheaper than a copy.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, otherwise it might panic as this could violate the assumptions.
+    pub fn write_eq(&self) -> WriteGuard<'_, T> {
+        self.write_synced_by(
+            |new_data, old_data| {
+                if new_data != old_data {
+                    new_data.clone_from(old_data);
+                }
+            },
+            "write_eq()",
+        )
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use crate::LeftRightBuffer;
+
+    #[test]
+    fn write_eq_syncs_the_published_data() {
+        let buffer = LeftRightBuffer::new([0u8; 32]);
+        let mut writer = buffer.write_eq();
+        writer[3] = 3;
+        buffer.publish(writer);
+
+        let mut writer = buffer.write_eq();
+        assert_eq!(writer[3], 3);
+        writer[4] = 4;
+        buffer.publish(writer);
+
+        let writer = buffer.write_eq();
+        assert_eq!(writer[..5], [0, 0, 0, 3, 4]);
+    }
+}
@@ -0,0 +1,151 @@
+//! Setpoint and feedback of a control loop, see [`ControlChannel`].
+
+use crate::{LeftRightBuffer, ReadGuard};
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// Pair of buffers between a supervisory task and a control loop, e.g. of a motor or a heater.
+///
+/// The supervisory task publishes the setpoint to the control loop and reads its feedback.
+/// The control loop, e.g. an interrupt, publishes the feedback in the reversed direction:
+/// its writer has the higher priority, so it skips a publication instead of violating the assumptions,
+/// if the supervisory task still holds the previous feedback, see [`Controller::publish_feedback()`].
+///
+/// ```
+/// use minimal_left_right::ControlChannel;
+///
+/// static HEATER: ControlChannel<u16, u16> = ControlChannel::new(0, 20);
+///
+/// let (supervisor, controller) = HEATER.split().expect("split once");
+/// supervisor.publish_setpoint(60);
+/// let setpoint = controller.load_setpoint();
+/// assert!(controller.publish_feedback(setpoint / 2));
+/// assert_eq!(supervisor.load_feedback(), 30);
+/// ```
+pub struct ControlChannel<SP, FB> {
+    setpoint: LeftRightBuffer<SP>,
+    feedback: LeftRightBuffer<FB>,
+    split: AtomicBool,
+}
+
+/// Handle of the supervisory task of a [`ControlChannel`], which writes the setpoint and reads the feedback.
+pub struct Supervisor<'a, SP, FB> {
+    channel: &'a ControlChannel<SP, FB>,
+}
+
+/// Handle of the control loop of a [`ControlChannel`], which reads the setpoint and writes the feedback.
+pub struct Controller<'a, SP, FB> {
+    channel: &'a ControlChannel<SP, FB>,
+}
+
+impl<SP: Copy, FB: Copy> ControlChannel<SP, FB> {
+    /// Generates a new [`ControlChannel`] with the initial setpoint and feedback.
+    #[cfg(not(any(loom, shuttle)))]
+    pub const fn new(setpoint: SP, feedback: FB) -> ControlChannel<SP, FB> {
+        ControlChannel {
+            setpoint: LeftRightBuffer::new(setpoint),
+            feedback: LeftRightBuffer::new(feedback),
+            split: AtomicBool::new(false),
+        }
+    }
+
+    /// Generates a new [`ControlChannel`] with the initial setpoint and feedback.
+    #[cfg(any(loom, shuttle))]
+    pub fn new(setpoint: SP, feedback: FB) -> ControlChannel<SP, FB> {
+        ControlChannel {
+            setpoint: LeftRightBuffer::new(setpoint),
+            feedback: LeftRightBuffer::new(feedback),
+            split: AtomicBool::new(false),
+        }
+    }
+
+    /// Returns the handles of both roles, so that each buffer has exactly one writer.
+    ///
+    /// Returns `None` if the channel got split before.
+    pub fn split(&self) -> Option<(Supervisor<'_, SP, FB>, Controller<'_, SP, FB>)> {
+        if self.split.swap(true, Ordering::AcqRel) {
+            return None;
+        }
+        Some((Supervisor { channel: self }, Controller { channel: self }))
+    }
+}
+
+impl<SP: Copy, FB: Copy> Supervisor<'_, SP, FB> {
+    /// Publishes a new setpoint for the control loop.
+    pub fn publish_setpoint(&self, setpoint: SP) {
+        self.channel.setpoint.store(setpoint);
+    }
+
+    /// Returns a read guard of the last feedback of the control loop.
+    ///
+    /// Release it quickly, as the control loop skips its publications while it is held.
+    #[must_use]
+    pub fn feedback(&self) -> ReadGuard<'_, FB> {
+        self.channel.feedback.read()
+    }
+
+    /// Returns a copy of the last feedback of the control loop.
+    #[must_use]
+    pub fn load_feedback(&self) -> FB {
+        self.channel.feedback.load()
+    }
+}
+
+impl<SP: Copy, FB: Copy> Controller<'_, SP, FB> {
+    /// Returns a read guard of the current setpoint.
+    #[must_use]
+    pub fn setpoint(&self) -> ReadGuard<'_, SP> {
+        self.channel.setpoint.read()
+    }
+
+    /// Returns a copy of the current setpoint.
+    #[must_use]
+    pub fn load_setpoint(&self) -> SP {
+        self.channel.setpoint.load()
+    }
+
+    /// Publishes the feedback for the supervisory task.
+    ///
+    /// If the preempted supervisory task still holds the previous feedback, the feedback gets skipped and `false` is returned,
+    /// as the control loop must neither wait nor panic. The next publication gets through again.
+    pub fn publish_feedback(&self, feedback: FB) -> bool {
+        let Ok(mut writer) = self.channel.feedback.try_write_without_sync() else {
+            return false;
+        };
+        *writer = feedback;
+        self.channel.feedback.publish(writer);
+        true
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_channel_only_splits_once() {
+        let channel = ControlChannel::new(0u8, 0i16);
+        assert!(channel.split().is_some());
+        assert!(channel.split().is_none(), "the roles are taken");
+    }
+
+    #[test]
+    fn a_held_feedback_skips_the_publication() {
+        let channel = ControlChannel::new(10u8, 0i16);
+        let (supervisor, controller) = channel.split().expect("split once");
+        assert_eq!(*controller.setpoint(), 10);
+        assert!(controller.publish_feedback(1));
+
+        let held = supervisor.feedback();
+        assert!(controller.publish_feedback(2), "the pending side is free");
+        assert!(
+            !controller.publish_feedback(3),
+            "the supervisor still holds the retired side"
+        );
+        assert_eq!(*held, 1);
+        drop(held);
+        assert_eq!(supervisor.load_feedback(), 2);
+        assert!(controller.publish_feedback(4));
+        assert_eq!(supervisor.load_feedback(), 4);
+    }
+}
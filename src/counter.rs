@@ -0,0 +1,109 @@
+//! Accumulating buffer, see [`LeftRightCounter`].
+
+use crate::{LeftRightBuffer, ReadGuard};
+use core::ops::Add;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// Buffer, whose publications add the pending value to the published one instead of replacing it.
+///
+/// The writer accumulates increments with [`add()`][LeftRightCounter::add], which stay invisible until
+/// [`publish()`][LeftRightCounter::publish] adds them to the total. The readers get the same guarantees as with
+/// a [`LeftRightBuffer`], e.g. for event counters or accumulated error statistics since boot.
+///
+/// The payload decides about overflows, e.g. `Wrapping<u32>` wraps around while `u32` panics in debug builds.
+pub struct LeftRightCounter<T> {
+    buffer: LeftRightBuffer<T>,
+    // The pending side still holds an old total instead of the increments since the last publication.
+    stale: AtomicBool,
+}
+
+impl<T: Copy + Default + Add<Output = T>> LeftRightCounter<T> {
+    /// Generates a new [`LeftRightCounter`] starting at `total`.
+    #[cfg(not(any(loom, shuttle)))]
+    pub const fn new(total: T) -> LeftRightCounter<T> {
+        LeftRightCounter {
+            buffer: LeftRightBuffer::new(total),
+            stale: AtomicBool::new(true),
+        }
+    }
+
+    /// Generates a new [`LeftRightCounter`] starting at `total`.
+    #[cfg(any(loom, shuttle))]
+    pub fn new(total: T) -> LeftRightCounter<T> {
+        LeftRightCounter {
+            buffer: LeftRightBuffer::new(total),
+            stale: AtomicBool::new(true),
+        }
+    }
+
+    /// Adds `increment` to the pending value, which becomes visible with the next publication.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, otherwise it might panic as this could violate the assumptions.
+    pub fn add(&self, increment: T) {
+        let mut pending = self.buffer.write_without_sync();
+        *pending = self.increments(*pending) + increment;
+    }
+
+    /// Adds the pending value to the published one and starts the pending value from scratch.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, otherwise it might panic as this could violate the assumptions.
+    pub fn publish(&self) {
+        let mut pending = self.buffer.write_without_sync();
+        *pending = *self.buffer.read() + self.increments(*pending);
+        self.buffer.publish(pending);
+        self.stale.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns the increments held by `pending`, which are none if it still holds an old total.
+    fn increments(&self, pending: T) -> T {
+        if self.stale.swap(false, Ordering::Relaxed) {
+            T::default()
+        } else {
+            pending
+        }
+    }
+
+    /// Returns a read guard of the published total, see [`LeftRightBuffer::read()`].
+    #[must_use]
+    pub fn read(&self) -> ReadGuard<'_, T> {
+        self.buffer.read()
+    }
+
+    /// Returns a copy of the published total, see [`LeftRightBuffer::load()`].
+    #[must_use]
+    pub fn load(&self) -> T {
+        self.buffer.load()
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+    use core::num::Wrapping;
+
+    #[test]
+    fn publications_accumulate() {
+        let errors = LeftRightCounter::new(10u32);
+        errors.add(1);
+        errors.add(2);
+        assert_eq!(errors.load(), 10, "the increments are pending");
+        errors.publish();
+        assert_eq!(errors.load(), 13);
+        errors.publish();
+        assert_eq!(errors.load(), 13, "there were no increments");
+        errors.add(4);
+        errors.publish();
+        assert_eq!(*errors.read(), 17);
+    }
+
+    #[test]
+    fn the_payload_decides_about_overflows() {
+        let events = LeftRightCounter::new(Wrapping(u8::MAX));
+        events.add(Wrapping(2));
+        events.publish();
+        assert_eq!(events.load(), Wrapping(1));
+    }
+}
@@ -0,0 +1,108 @@
+//! Readers consuming only every Nth publication, see [`DecimatedReader`].
+
+use crate::{LeftRightBuffer, ReadGuard};
+use core::sync::atomic::{AtomicU32, Ordering};
+
+/// Read access of a [`LeftRightBuffer`], which only consumes every Nth publication.
+///
+/// Each consumer gets its own reader with its own decimation factor, tracked with the [`generation()`][LeftRightBuffer::generation].
+/// E.g. a 1 kHz writer feeds a 50 Hz logger with the factor 20 and a 1 kHz control loop with the factor 1 from the same buffer.
+///
+/// ```
+/// use minimal_left_right::{DecimatedReader, LeftRightBuffer};
+///
+/// let samples = LeftRightBuffer::new(0u16);
+/// let logger = DecimatedReader::new(&samples, 2);
+/// assert_eq!(logger.read_decimated().as_deref(), Some(&0));
+/// samples.store(1);
+/// assert!(logger.read_decimated().is_none(), "the first publication gets skipped");
+/// samples.store(2);
+/// assert_eq!(logger.read_decimated().as_deref(), Some(&2));
+/// ```
+pub struct DecimatedReader<'a, T> {
+    buffer: &'a LeftRightBuffer<T>,
+    factor: u32,
+    // The generation of the last consumed value.
+    consumed: AtomicU32,
+}
+
+impl<'a, T: Clone> DecimatedReader<'a, T> {
+    /// Generates a new [`DecimatedReader`] of `buffer`, which consumes every `factor`th publication.
+    ///
+    /// The factor 0 behaves like 1, which consumes every publication. The first read consumes the current value.
+    pub fn new(buffer: &'a LeftRightBuffer<T>, factor: u32) -> DecimatedReader<'a, T> {
+        let factor = factor.max(1);
+        DecimatedReader {
+            buffer,
+            factor,
+            consumed: AtomicU32::new(buffer.generation().get().wrapping_sub(factor)),
+        }
+    }
+
+    /// Returns a read guard, if at least `factor` publications happened since the last consumed value.
+    ///
+    /// Returns `None` for the skipped publications. If the reader polls less often than the writer publishes,
+    /// it consumes the newest value as soon as the factor is reached.
+    #[must_use]
+    pub fn read_decimated(&self) -> Option<ReadGuard<'a, T>> {
+        let generation = self.buffer.generation().get();
+        let consumed = self.consumed.load(Ordering::Relaxed);
+        if generation.wrapping_sub(consumed) < self.factor {
+            return None;
+        }
+        self.consumed.store(generation, Ordering::Relaxed);
+        Some(self.buffer.read())
+    }
+
+    /// Returns a read guard of the published value regardless of the decimation, see [`read()`][LeftRightBuffer::read].
+    #[must_use]
+    pub fn read(&self) -> ReadGuard<'a, T> {
+        self.buffer.read()
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_reader_has_its_own_factor() {
+        let samples = LeftRightBuffer::new(0u32);
+        let control = DecimatedReader::new(&samples, 0);
+        let logger = DecimatedReader::new(&samples, 20);
+        let mut controlled = 0;
+        let mut entries = [0; 3];
+        let mut logs = 0;
+        assert!(
+            logger.read_decimated().is_some(),
+            "the first read consumes the initial value"
+        );
+        for sample in 1..=60 {
+            samples.store(sample);
+            if control.read_decimated().is_some() {
+                controlled += 1;
+            }
+            if let Some(value) = logger.read_decimated() {
+                entries[logs] = *value;
+                logs += 1;
+            }
+        }
+        assert_eq!(controlled, 60, "the factor 0 consumes every publication");
+        assert_eq!(logs, 3);
+        assert_eq!(entries, [20, 40, 60], "every 20th publication got consumed");
+    }
+
+    #[test]
+    fn a_slow_reader_consumes_the_newest_value() {
+        let samples = LeftRightBuffer::new(0u32);
+        let logger = DecimatedReader::new(&samples, 3);
+        assert!(logger.read_decimated().is_some(), "the first read consumes");
+        for sample in 1..=7 {
+            samples.store(sample);
+        }
+        assert_eq!(logger.read_decimated().as_deref(), Some(&7));
+        assert!(logger.read_decimated().is_none());
+        assert_eq!(*logger.read(), 7, "the plain read ignores the decimation");
+    }
+}
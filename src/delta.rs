@@ -0,0 +1,144 @@
+use crate::{LeftRightBuffer, WriteGuard};
+use core::cmp::Ordering;
+
+/// Payloads whose distance to another value can be measured.
+///
+/// Used by [`publish_if_delta_exceeds()`][LeftRightBuffer::publish_if_delta_exceeds] to suppress publications of insignificant changes.
+pub trait DeltaOrd {
+    /// The type describing the distance between two values.
+    type Delta: PartialOrd + Copy;
+
+    /// Returns the absolute distance between `self` and `other`.
+    fn delta(&self, other: &Self) -> Self::Delta;
+}
+
+macro_rules! impl_delta_ord_int {
+    ($($t:ty => $d:ty),*) => {
+        $(
+            impl DeltaOrd for $t {
+                type Delta = $d;
+
+                fn delta(&self, other: &Self) -> $d {
+                    self.abs_diff(*other)
+                }
+            }
+        )*
+    };
+}
+
+impl_delta_ord_int!(
+    u8 => u8, u16 => u16, u32 => u32, u64 => u64, u128 => u128, usize => usize,
+    i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize
+);
+
+macro_rules! impl_delta_ord_float {
+    ($($t:ty),*) => {
+        $(
+            impl DeltaOrd for $t {
+                type Delta = $t;
+
+                fn delta(&self, other: &Self) -> $t {
+                    (self - other).abs()
+                }
+            }
+        )*
+    };
+}
+
+impl_delta_ord_float!(f32, f64);
+
+impl<T: Copy + DeltaOrd> LeftRightBuffer<T> {
+    /// Publishes the pending value only if it differs from the published value by more than `threshold`.
+    ///
+    /// Returns `true` if the value got published.
+    /// Otherwise the writer is dropped without publishing. The pending value is kept,
+    /// so small changes accumulate until they exceed the threshold with respect to the published value.
+    ///
+    /// A delta, which cannot be compared with the threshold, counts as exceeding it. Otherwise a published NaN,
+    /// a published infinity followed by the same infinity or a NaN threshold would block all further publications.
+    pub fn publish_if_delta_exceeds(&self, writer: WriteGuard<'_, T>, threshold: T::Delta) -> bool {
+        let exceeds = writer
+            .delta(&self.read())
+            .partial_cmp(&threshold)
+            .is_none_or(Ordering::is_gt);
+        if exceeds {
+            self.publish(writer);
+        }
+        exceeds
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use crate::LeftRightBuffer;
+
+    #[test]
+    fn small_changes_accumulate_until_they_exceed_the_threshold() {
+        let buffer = LeftRightBuffer::new(100u16);
+
+        let mut writer = buffer.write();
+        *writer = 102;
+        assert!(
+            !buffer.publish_if_delta_exceeds(writer, 3),
+            "jitter must not be published"
+        );
+        assert_eq!(*buffer.read(), 100);
+
+        let mut writer = buffer.write();
+        *writer += 2;
+        assert!(
+            buffer.publish_if_delta_exceeds(writer, 3),
+            "drift must be published"
+        );
+        assert_eq!(*buffer.read(), 104);
+    }
+
+    #[test]
+    fn signed_and_float_deltas_are_absolute() {
+        let buffer = LeftRightBuffer::new(0i32);
+        let mut writer = buffer.write();
+        *writer = -5;
+        assert!(
+            buffer.publish_if_delta_exceeds(writer, 4),
+            "the delta of -5 must exceed 4"
+        );
+
+        let buffer = LeftRightBuffer::new(1.0f32);
+        let mut writer = buffer.write();
+        *writer = 0.95;
+        assert!(
+            !buffer.publish_if_delta_exceeds(writer, 0.1),
+            "the delta of 0.05 must not exceed 0.1"
+        );
+    }
+    #[test]
+    fn unordered_deltas_get_published() {
+        let buffer = LeftRightBuffer::new(1.0f32);
+        let mut writer = buffer.write();
+        *writer = f32::NAN;
+        assert!(
+            buffer.publish_if_delta_exceeds(writer, 0.1),
+            "NaN must be published"
+        );
+
+        let mut writer = buffer.write();
+        *writer = 2.0;
+        assert!(
+            buffer.publish_if_delta_exceeds(writer, 0.1),
+            "the buffer must recover from NaN"
+        );
+        assert_eq!(
+            buffer.read().to_bits(),
+            2.0f32.to_bits(),
+            "the finite value must be published"
+        );
+
+        let mut writer = buffer.write();
+        *writer = 2.01;
+        assert!(
+            buffer.publish_if_delta_exceeds(writer, f32::NAN),
+            "a NaN threshold must not block publications"
+        );
+    }
+}
@@ -0,0 +1,131 @@
+//! Change notifications of the `diff` feature, see [`publish_diff()`][crate::LeftRightBuffer::publish_diff].
+
+use crate::{LeftRightBuffer, READ_LEFT, READ_RIGHT, WriteGuard, ordering};
+
+/// Payloads which report their changed fields.
+///
+/// ```
+/// use minimal_left_right::Diff;
+///
+/// #[derive(Clone)]
+/// struct Display {
+///     header: u32,
+///     body: u32,
+/// }
+///
+/// impl Diff for Display {
+///     fn diff(&self, previous: &Display) -> u32 {
+///         u32::from(self.header != previous.header) | (u32::from(self.body != previous.body) << 1)
+///     }
+/// }
+/// ```
+pub trait Diff {
+    /// Returns a bit mask of the fields which differ from `previous`, one bit per field.
+    fn diff(&self, previous: &Self) -> u32;
+}
+
+/// Bit mask of the changes, if they are unknown.
+pub const ALL_CHANGED: u32 = u32::MAX;
+
+/// Hook invoked on every publication with the name of the buffer and the bit mask of the changed fields.
+///
+/// [`publish()`][LeftRightBuffer::publish] reports [`ALL_CHANGED`], [`publish_diff()`][LeftRightBuffer::publish_diff] the result of [`Diff::diff()`].
+pub type PublishHook = fn(name: Option<&'static str>, changes: u32);
+
+static PUBLISH_HOOK: spin::Once<PublishHook> = spin::Once::new();
+
+/// Registers the hook, which gets invoked on every publication.
+///
+/// The hook can only be registered once. Returns `false` if there was already a hook.
+pub fn set_publish_hook(hook: PublishHook) -> bool {
+    let mut registered = false;
+    PUBLISH_HOOK.call_once(|| {
+        registered = true;
+        hook
+    });
+    registered
+}
+
+/// Invokes the registered hook.
+pub(crate) fn notify(name: Option<&'static str>, changes: u32) {
+    if let Some(hook) = PUBLISH_HOOK.get() {
+        hook(name, changes);
+    }
+}
+
+impl<T: Clone + Diff> LeftRightBuffer<T> {
+    /// Makes the value of the write guard visible to the readers like [`publish()`][LeftRightBuffer::publish],
+    /// but reports the fields which changed relative to the previously published value to the [`PublishHook`].
+    ///
+    /// Consumers can then skip the work for unchanged parts, e.g. only re-render the changed parts of a display.
+    /// If the published value cannot be read, [`ALL_CHANGED`] gets reported.
+    pub fn publish_diff(&self, writer: WriteGuard<'_, T>) {
+        let published = match self.direction(ordering::RELAXED) {
+            READ_RIGHT => self.right.try_read(),
+            READ_LEFT => self.left.try_read(),
+        };
+        let changes = published.map_or(ALL_CHANGED, |published| writer.diff(&published));
+        self.swap_sides(writer, false, "publish_diff()");
+        notify(self.name, changes);
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+
+    std::thread_local! {
+        static CHANGES: Cell<Option<u32>> = const { Cell::new(None) };
+    }
+
+    fn record_changes(name: Option<&'static str>, changes: u32) {
+        if name == Some("diff") {
+            CHANGES.with(|recorded| recorded.set(Some(changes)));
+        }
+    }
+
+    #[derive(Clone, Copy)]
+    struct Pair {
+        first: u8,
+        second: u8,
+    }
+
+    impl Diff for Pair {
+        fn diff(&self, previous: &Pair) -> u32 {
+            u32::from(self.first != previous.first)
+                | (u32::from(self.second != previous.second) << 1)
+        }
+    }
+
+    #[test]
+    fn publications_report_the_changed_fields() {
+        assert!(
+            set_publish_hook(record_changes),
+            "no other test registers a hook"
+        );
+        let buffer = LeftRightBuffer::new(Pair {
+            first: 0,
+            second: 0,
+        })
+        .with_name("diff");
+
+        let mut writer = buffer.write();
+        writer.second = 1;
+        buffer.publish_diff(writer);
+        assert_eq!(CHANGES.with(Cell::get), Some(0b10));
+
+        let mut writer = buffer.write();
+        writer.first = 1;
+        writer.second = 1;
+        buffer.publish_diff(writer);
+        assert_eq!(CHANGES.with(Cell::get), Some(0b01));
+
+        buffer.store(Pair {
+            first: 1,
+            second: 1,
+        });
+        assert_eq!(CHANGES.with(Cell::get), Some(ALL_CHANGED));
+    }
+}
@@ -0,0 +1,70 @@
+//! Durations of [fugit](https://crates.io/crates/fugit) for the time-based APIs of the `fugit` feature.
+//!
+//! The durations count in ticks of the registered [`Clock`], so their tick rate `NOM / DENOM` seconds has to match the clock,
+//! e.g. `TimerDurationU32<1_000_000>` for a 1 MHz timer. Durations of other rates get converted with `convert()` at compile time.
+
+use crate::Clock;
+
+/// [`Clock`] reading a monotonic timer, which returns [fugit](https://crates.io/crates/fugit) instants,
+/// e.g. the `now()` of a monotonic of [rtic-monotonics](https://crates.io/crates/rtic-monotonics).
+///
+/// The ticks of 64 bit instants get truncated, which wraps around like every [`Clock`].
+pub struct MonotonicClock<const NOM: u32, const DENOM: u32> {
+    now: Now<NOM, DENOM>,
+}
+
+/// Time source of a [`MonotonicClock`].
+enum Now<const NOM: u32, const DENOM: u32> {
+    U32(fn() -> fugit::Instant<u32, NOM, DENOM>),
+    U64(fn() -> fugit::Instant<u64, NOM, DENOM>),
+}
+
+impl<const NOM: u32, const DENOM: u32> MonotonicClock<NOM, DENOM> {
+    /// Generates a new [`MonotonicClock`] reading the 32 bit instants of `now`, e.g. `MonotonicClock::new(Mono::now)`.
+    #[must_use]
+    pub const fn new(now: fn() -> fugit::Instant<u32, NOM, DENOM>) -> MonotonicClock<NOM, DENOM> {
+        MonotonicClock { now: Now::U32(now) }
+    }
+
+    /// Generates a new [`MonotonicClock`] reading the 64 bit instants of `now`.
+    #[must_use]
+    pub const fn new_u64(
+        now: fn() -> fugit::Instant<u64, NOM, DENOM>,
+    ) -> MonotonicClock<NOM, DENOM> {
+        MonotonicClock { now: Now::U64(now) }
+    }
+}
+
+impl<const NOM: u32, const DENOM: u32> Clock for MonotonicClock<NOM, DENOM> {
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "the ticks wrap around like every clock"
+    )]
+    fn now(&self) -> u32 {
+        match self.now {
+            Now::U32(now) => now().ticks(),
+            Now::U64(now) => now().ticks() as u32,
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+
+    fn late() -> fugit::TimerInstantU64<1_000> {
+        fugit::TimerInstantU64::from_ticks(u64::from(u32::MAX) + 6)
+    }
+
+    #[test]
+    fn the_monotonic_instants_become_ticks() {
+        let clock = MonotonicClock::new(|| fugit::TimerInstantU32::<1_000>::from_ticks(7));
+        assert_eq!(clock.now(), 7);
+        assert_eq!(
+            MonotonicClock::new_u64(late).now(),
+            5,
+            "the 64 bit ticks wrap around"
+        );
+    }
+}
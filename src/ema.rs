@@ -0,0 +1,111 @@
+//! Filtering buffer, see [`LeftRightEma`].
+
+use crate::{LeftRightBuffer, ReadGuard};
+use core::ops::{Add, Mul, Sub};
+
+/// Buffer, whose writer pushes raw samples and whose readers get the exponential moving average of them.
+///
+/// Every sample of [`push()`][LeftRightEma::push] moves the pending estimate by `alpha` towards the sample.
+/// The estimate stays invisible until [`publish()`][LeftRightEma::publish], so the readers get the same guarantees as with
+/// a [`LeftRightBuffer`], e.g. for the filtered value of a noisy sensor sampled faster than it gets published.
+///
+/// ```
+/// use minimal_left_right::LeftRightEma;
+///
+/// let temperature = LeftRightEma::new(20.0f32, 0.5);
+/// temperature.push(30.0);
+/// temperature.push(30.0);
+/// temperature.publish();
+/// assert_eq!(temperature.load().to_bits(), 27.5f32.to_bits());
+/// ```
+pub struct LeftRightEma<T> {
+    buffer: LeftRightBuffer<T>,
+    alpha: T,
+}
+
+impl<T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>> LeftRightEma<T> {
+    /// Generates a new [`LeftRightEma`] starting at `estimate`, which weights every new sample with `alpha`.
+    ///
+    /// An `alpha` between 0 and 1 filters, the smaller the smoother. Use a floating point or a fixed point type for it.
+    #[cfg(not(any(loom, shuttle)))]
+    pub const fn new(estimate: T, alpha: T) -> LeftRightEma<T> {
+        LeftRightEma {
+            buffer: LeftRightBuffer::new(estimate),
+            alpha,
+        }
+    }
+
+    /// Generates a new [`LeftRightEma`] starting at `estimate`, which weights every new sample with `alpha`.
+    #[cfg(any(loom, shuttle))]
+    pub fn new(estimate: T, alpha: T) -> LeftRightEma<T> {
+        LeftRightEma {
+            buffer: LeftRightBuffer::new(estimate),
+            alpha,
+        }
+    }
+
+    /// Filters `sample` into the pending estimate, which becomes visible with the next publication.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, otherwise it might panic as this could violate the assumptions.
+    pub fn push(&self, sample: T) {
+        let mut pending = self.buffer.write();
+        *pending = *pending + self.alpha * (sample - *pending);
+    }
+
+    /// Publishes the pending estimate.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, otherwise it might panic as this could violate the assumptions.
+    pub fn publish(&self) {
+        let pending = self.buffer.write();
+        self.buffer.publish(pending);
+    }
+
+    /// Returns a read guard of the published estimate, see [`LeftRightBuffer::read()`].
+    #[must_use]
+    pub fn read(&self) -> ReadGuard<'_, T> {
+        self.buffer.read()
+    }
+
+    /// Returns a copy of the published estimate, see [`LeftRightBuffer::load()`].
+    #[must_use]
+    pub fn load(&self) -> T {
+        self.buffer.load()
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_estimate_follows_the_samples() {
+        let level = LeftRightEma::new(0.0f64, 0.25);
+        level.push(8.0);
+        assert_eq!(
+            level.load().to_bits(),
+            0.0f64.to_bits(),
+            "the estimate is pending"
+        );
+        level.publish();
+        assert_eq!(level.read().to_bits(), 2.0f64.to_bits());
+
+        level.push(10.0);
+        level.push(10.0);
+        assert_eq!(level.load().to_bits(), 2.0f64.to_bits());
+        level.publish();
+        assert_eq!(
+            level.load().to_bits(),
+            5.5f64.to_bits(),
+            "the pending estimate survives the publication"
+        );
+        level.publish();
+        assert_eq!(
+            level.load().to_bits(),
+            5.5f64.to_bits(),
+            "there were no samples"
+        );
+    }
+}
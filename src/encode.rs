@@ -0,0 +1,121 @@
+//! Wire frames of the `encode` feature, see [`publish_encoded()`][crate::LeftRightBuffer::publish_encoded].
+
+use crate::{LeftRightBuffer, WriteGuard};
+
+/// Payloads which serialize into a byte frame, e.g. for a telemetry, CAN or UART bridge.
+///
+/// ```
+/// use minimal_left_right::Encoder;
+///
+/// #[derive(Clone)]
+/// struct Speed {
+///     rpm: u16,
+/// }
+///
+/// impl Encoder for Speed {
+///     fn encode(&self, frame: &mut [u8]) -> Option<usize> {
+///         frame.get_mut(..2)?.copy_from_slice(&self.rpm.to_le_bytes());
+///         Some(2)
+///     }
+/// }
+/// ```
+pub trait Encoder {
+    /// Serializes the value into the start of `frame` and returns the length of the frame.
+    ///
+    /// Returns `None` if `frame` is too small.
+    fn encode(&self, frame: &mut [u8]) -> Option<usize>;
+}
+
+/// Sink invoked with the name of the buffer and the wire frame of every encoded publication.
+pub type EncodeSink = fn(name: Option<&'static str>, frame: &[u8]);
+
+static ENCODE_SINK: spin::Once<EncodeSink> = spin::Once::new();
+
+/// Registers the sink, which receives the frames of [`publish_encoded()`][LeftRightBuffer::publish_encoded].
+///
+/// The sink can only be registered once. Returns `false` if there was already a sink.
+pub fn set_encode_sink(sink: EncodeSink) -> bool {
+    let mut registered = false;
+    ENCODE_SINK.call_once(|| {
+        registered = true;
+        sink
+    });
+    registered
+}
+
+impl<T: Clone + Encoder> LeftRightBuffer<T> {
+    /// Makes the value of the write guard visible to the readers like [`publish()`][LeftRightBuffer::publish]
+    /// and hands its wire frame of at most `N` bytes to the registered [`EncodeSink`].
+    ///
+    /// The value gets encoded once by the writer, so the consumers of the bridge do not serialize it again.
+    /// Returns `false` if no frame got handed to the sink, because there is no sink or the frame does not fit into `N` bytes.
+    /// The value gets published in either case.
+    ///
+    /// # Panics
+    /// The guard belongs to another buffer or to a side, which got published meanwhile, see [`Violation::ForeignGuard`][crate::Violation::ForeignGuard].
+    pub fn publish_encoded<const N: usize>(&self, writer: WriteGuard<'_, T>) -> bool {
+        let mut frame = [0; N];
+        let len = writer.encode(&mut frame);
+        self.publish(writer);
+        let (Some(sink), Some(frame)) = (ENCODE_SINK.get(), len.and_then(|len| frame.get(..len)))
+        else {
+            return false;
+        };
+        sink(self.name, frame);
+        true
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+    use core::cell::RefCell;
+    use std::vec::Vec;
+
+    std::thread_local! {
+        static FRAMES: RefCell<Vec<Vec<u8>>> = const { RefCell::new(Vec::new()) };
+    }
+
+    fn record_frame(name: Option<&'static str>, frame: &[u8]) {
+        if name == Some("encode") {
+            FRAMES.with(|frames| frames.borrow_mut().push(frame.to_vec()));
+        }
+    }
+
+    #[derive(Clone, Copy)]
+    struct Reading(u32);
+
+    impl Encoder for Reading {
+        fn encode(&self, frame: &mut [u8]) -> Option<usize> {
+            frame.get_mut(..4)?.copy_from_slice(&self.0.to_be_bytes());
+            Some(4)
+        }
+    }
+
+    #[test]
+    fn publications_hand_the_frame_to_the_sink() {
+        assert!(
+            set_encode_sink(record_frame),
+            "no other test registers a sink"
+        );
+        let buffer = LeftRightBuffer::new(Reading(0)).with_name("encode");
+
+        let mut writer = buffer.write();
+        writer.0 = 0x0102_0304;
+        assert!(buffer.publish_encoded::<8>(writer));
+        assert_eq!(buffer.read().0, 0x0102_0304);
+
+        let mut writer = buffer.write();
+        writer.0 = 5;
+        assert!(
+            !buffer.publish_encoded::<2>(writer),
+            "the frame does not fit"
+        );
+        assert_eq!(buffer.read().0, 5, "the value got published anyway");
+
+        FRAMES.with(|frames| {
+            assert_eq!(*frames.borrow(), [[1, 2, 3, 4]]);
+        });
+    }
+}
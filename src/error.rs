@@ -0,0 +1,240 @@
+use crate::Side;
+#[cfg(any(test, not(feature = "fmt-free")))]
+use core::fmt;
+
+/// Errors reported by the fallible functions of this crate.
+#[cfg_attr(any(test, not(feature = "fmt-free")), derive(Debug))]
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// A lock could not be acquired within the given number of retries.
+    ///
+    /// This can only happen if the assumptions were violated.
+    Contention,
+    /// An assumption was violated.
+    Violation(Violation),
+    /// A writer panicked while holding the write guard.
+    Poisoned,
+    /// The bytes are no snapshot of the payload, see `load_snapshot()` of the `snapshot` feature.
+    InvalidSnapshot,
+    /// The serialized value does not fit into the output buffer, see `to_postcard()` of the `postcard` feature.
+    BufferTooSmall,
+    /// The previously published value got overwritten by a writer, see [`revert()`][crate::LeftRightBuffer::revert].
+    NoPrevious,
+}
+
+/// A violated assumption of a [`LeftRightBuffer`][crate::LeftRightBuffer].
+#[cfg_attr(any(test, not(feature = "fmt-free")), derive(Debug))]
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Violation {
+    /// The given side could not be locked for writing, as there is already a writer.
+    Write(Side),
+    /// The sync could not lock the given published side for reading.
+    SyncRead(Side),
+    /// The sync could not lock the given pending side for writing.
+    SyncWrite(Side),
+    /// The writer has a higher priority than the last reader, see [`PriorityCallback`][crate::PriorityCallback].
+    Priority {
+        /// The priority of the writer.
+        writer: u8,
+        /// The priority of the last reader.
+        reader: u8,
+    },
+    /// The buffer got sealed, see [`seal()`][crate::LeftRightBuffer::seal].
+    Sealed,
+    /// The published guard belongs to another buffer or to a side, which got published meanwhile.
+    ForeignGuard,
+    /// The buffer got published before its initialization, see [`init_from()`][crate::LeftRightBuffer::init_from].
+    Initialized,
+    /// The reader found both sides held by writers, e.g. as it interrupted [`clear()`][crate::LeftRightBuffer::clear].
+    Read,
+    /// A writer started while there was already one, e.g. of a [`LeftRightAtomic`][crate::LeftRightAtomic], which has no sides to lock.
+    SecondWriter,
+}
+
+impl Error {
+    /// Returns the numeric code of the error, see [`Violation::code()`] for violations.
+    ///
+    /// | Code | Error |
+    /// |---|---|
+    /// | 0x81 | [`Error::Contention`] |
+    /// | 0x82 | [`Error::Poisoned`] |
+    /// | 0x83 | [`Error::InvalidSnapshot`] |
+    /// | 0x84 | [`Error::NoPrevious`] |
+    /// | 0x85 | [`Error::BufferTooSmall`] |
+    #[must_use]
+    pub const fn code(self) -> u8 {
+        match self {
+            Error::Violation(violation) => violation.code(),
+            Error::Contention => 0x81,
+            Error::Poisoned => 0x82,
+            Error::InvalidSnapshot => 0x83,
+            Error::NoPrevious => 0x84,
+            Error::BufferTooSmall => 0x85,
+        }
+    }
+
+    /// Returns the message of the error, see [`Violation::message()`] for violations.
+    #[cfg(any(test, feature = "ufmt", not(feature = "fmt-free")))]
+    pub(crate) fn message(self) -> &'static str {
+        match self {
+            Error::Contention => "LRBuffer contention",
+            Error::Violation(violation) => violation.message(),
+            Error::Poisoned => "LRBuffer poisoned",
+            Error::InvalidSnapshot => "LRBuffer invalid snapshot",
+            Error::BufferTooSmall => "LRBuffer buffer too small",
+            Error::NoPrevious => "LRBuffer no previous",
+        }
+    }
+}
+
+impl Violation {
+    /// Returns the numeric code of the violation, which identifies it without any formatting, e.g. in a [`FaultHandler`][crate::FaultHandler] of a `fmt-free` build.
+    ///
+    /// The codes 1 to 6 match the numbers of the messages:
+    ///
+    /// | Code | Violation | Message |
+    /// |---|---|---|
+    /// | 1 | [`Violation::Write`]`(Left)` | `LRBuffer write1` |
+    /// | 2 | [`Violation::Write`]`(Right)` | `LRBuffer write2` |
+    /// | 3 | [`Violation::SyncRead`]`(Right)` | `LRBuffer sync1` |
+    /// | 4 | [`Violation::SyncWrite`]`(Left)` | `LRBuffer sync2` |
+    /// | 5 | [`Violation::SyncRead`]`(Left)` | `LRBuffer sync3` |
+    /// | 6 | [`Violation::SyncWrite`]`(Right)` | `LRBuffer sync4` |
+    /// | 7 | [`Violation::Priority`] | `LRBuffer priority` |
+    /// | 8 | [`Violation::Sealed`] | `LRBuffer sealed` |
+    /// | 9 | [`Violation::ForeignGuard`] | `LRBuffer foreign guard` |
+    /// | 10 | [`Violation::Initialized`] | `LRBuffer initialized` |
+    /// | 11 | [`Violation::Read`] | `LRBuffer read` |
+    /// | 12 | [`Violation::SecondWriter`] | `LRBuffer second writer` |
+    #[must_use]
+    pub const fn code(self) -> u8 {
+        match self {
+            Violation::Write(Side::Left) => 1,
+            Violation::Write(Side::Right) => 2,
+            Violation::SyncRead(Side::Right) => 3,
+            Violation::SyncWrite(Side::Left) => 4,
+            Violation::SyncRead(Side::Left) => 5,
+            Violation::SyncWrite(Side::Right) => 6,
+            Violation::Priority { .. } => 7,
+            Violation::Sealed => 8,
+            Violation::ForeignGuard => 9,
+            Violation::Initialized => 10,
+            Violation::Read => 11,
+            Violation::SecondWriter => 12,
+        }
+    }
+
+    /// Returns the message of the violation. The priorities of [`Violation::Priority`] are appended by the formatters.
+    #[cfg(any(test, feature = "ufmt", not(feature = "fmt-free")))]
+    pub(crate) fn message(self) -> &'static str {
+        match self {
+            Violation::Write(Side::Left) => "LRBuffer write1",
+            Violation::Write(Side::Right) => "LRBuffer write2",
+            Violation::SyncRead(Side::Right) => "LRBuffer sync1",
+            Violation::SyncWrite(Side::Left) => "LRBuffer sync2",
+            Violation::SyncRead(Side::Left) => "LRBuffer sync3",
+            Violation::SyncWrite(Side::Right) => "LRBuffer sync4",
+            Violation::Priority { .. } => "LRBuffer priority",
+            Violation::Sealed => "LRBuffer sealed",
+            Violation::ForeignGuard => "LRBuffer foreign guard",
+            Violation::Initialized => "LRBuffer initialized",
+            Violation::Read => "LRBuffer read",
+            Violation::SecondWriter => "LRBuffer second writer",
+        }
+    }
+
+    /// Returns the explanation of the violation for the panic messages.
+    #[cfg(not(feature = "panic-free"))]
+    pub(crate) fn detail(self) -> &'static str {
+        match self {
+            Violation::Write(Side::Left) => "the left side is held by a reader or another writer",
+            Violation::Write(Side::Right) => "the right side is held by a reader or another writer",
+            Violation::SyncRead(Side::Left) => {
+                "the published left side is locked, so it cannot be synced"
+            }
+            Violation::SyncRead(Side::Right) => {
+                "the published right side is locked, so it cannot be synced"
+            }
+            Violation::SyncWrite(Side::Left) => {
+                "the pending left side is held by a reader, so it cannot be synced"
+            }
+            Violation::SyncWrite(Side::Right) => {
+                "the pending right side is held by a reader, so it cannot be synced"
+            }
+            Violation::Priority { .. } => "the writer has a higher priority than the last reader",
+            Violation::Sealed => "the buffer is sealed",
+            Violation::ForeignGuard => {
+                "the guard belongs to another buffer or to a side, which got published meanwhile"
+            }
+            Violation::Initialized => "the buffer got published before its initialization",
+            Violation::Read => "both sides are held by writers, so there is no value to read",
+            Violation::SecondWriter => "there is already a writer",
+        }
+    }
+}
+
+#[cfg(any(test, not(feature = "fmt-free")))]
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Violation(violation) => violation.fmt(f),
+            _ => f.write_str(self.message()),
+        }
+    }
+}
+
+#[cfg(any(test, not(feature = "fmt-free")))]
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())?;
+        if let Violation::Priority { writer, reader } = self {
+            write!(f, " {writer} > {reader}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(any(test, not(feature = "fmt-free")))]
+impl core::error::Error for Error {}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_codes_identify_the_errors() {
+        let errors = [
+            Error::Violation(Violation::Write(Side::Left)),
+            Error::Violation(Violation::Write(Side::Right)),
+            Error::Violation(Violation::SyncRead(Side::Right)),
+            Error::Violation(Violation::SyncWrite(Side::Left)),
+            Error::Violation(Violation::SyncRead(Side::Left)),
+            Error::Violation(Violation::SyncWrite(Side::Right)),
+            Error::Violation(Violation::Priority {
+                writer: 2,
+                reader: 1,
+            }),
+            Error::Violation(Violation::Sealed),
+            Error::Violation(Violation::ForeignGuard),
+            Error::Violation(Violation::Initialized),
+            Error::Violation(Violation::Read),
+            Error::Violation(Violation::SecondWriter),
+            Error::Contention,
+            Error::Poisoned,
+            Error::InvalidSnapshot,
+            Error::NoPrevious,
+            Error::BufferTooSmall,
+        ];
+        let codes = errors.map(Error::code);
+        assert_eq!(codes[..12], [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]);
+        assert_eq!(codes[12..], [0x81, 0x82, 0x83, 0x84, 0x85]);
+        assert_eq!(
+            Error::Violation(Violation::SyncRead(Side::Left)).message(),
+            "LRBuffer sync3",
+            "the code matches the number of the message"
+        );
+    }
+}
@@ -0,0 +1,135 @@
+//! Aggregation of several producers into one published value, see [`FanIn`].
+
+use crate::{LeftRightBuffer, ReadGuard};
+
+/// Combination of the contributions to a [`FanIn`].
+pub trait Merge {
+    /// Folds the `contribution` of another producer into `self`.
+    fn merge(&mut self, contribution: &Self);
+}
+
+/// Up to `N` producers, whose contributions get merged into one published value.
+///
+/// Each producer owns a private slot, which is a [`LeftRightBuffer`] on its own, so the producers never block each other.
+/// A coordinator calls [`merge_and_publish()`][FanIn::merge_and_publish] to combine the published contributions with [`Merge`]
+/// and to publish the result to the readers of [`output()`][FanIn::output].
+///
+/// ```
+/// use minimal_left_right::{FanIn, Merge};
+///
+/// #[derive(Clone, Default)]
+/// struct Fused {
+///     temperature: Option<i16>,
+///     pressure: Option<u16>,
+/// }
+///
+/// impl Merge for Fused {
+///     fn merge(&mut self, contribution: &Fused) {
+///         self.temperature = self.temperature.or(contribution.temperature);
+///         self.pressure = self.pressure.or(contribution.pressure);
+///     }
+/// }
+///
+/// let fused = FanIn::<Fused, 2>::new(Fused::default());
+/// let [thermometer, barometer] = fused.slots();
+/// let mut reading = thermometer.write();
+/// reading.temperature = Some(21);
+/// thermometer.publish(reading);
+/// barometer.store(Fused { temperature: None, pressure: Some(1013) });
+///
+/// fused.merge_and_publish();
+/// assert_eq!(fused.output().read().temperature, Some(21));
+/// assert_eq!(fused.output().read().pressure, Some(1013));
+/// ```
+pub struct FanIn<T, const N: usize> {
+    slots: [LeftRightBuffer<T>; N],
+    output: LeftRightBuffer<T>,
+}
+
+impl<T: Clone + Merge, const N: usize> FanIn<T, N> {
+    /// Generates a new [`FanIn`], whose slots and output start with the data.
+    pub fn new(data: T) -> FanIn<T, N> {
+        FanIn {
+            slots: core::array::from_fn(|_| LeftRightBuffer::new_cloned(data.clone())),
+            output: LeftRightBuffer::new_cloned(data),
+        }
+    }
+
+    /// Returns the private slot of the producer with the given `index`, or `None` if it is out of range.
+    ///
+    /// The producer writes and publishes its contribution like with any other buffer.
+    pub fn slot(&self, index: usize) -> Option<&LeftRightBuffer<T>> {
+        self.slots.get(index)
+    }
+
+    /// Returns the slots of all producers, e.g. to hand them out with a destructuring pattern.
+    pub fn slots(&self) -> &[LeftRightBuffer<T>; N] {
+        &self.slots
+    }
+
+    /// Merges the published contributions of all slots and publishes the result to the output.
+    ///
+    /// The result starts from the contribution of the first slot, into which the others get merged in slot order.
+    ///
+    /// # Panics
+    /// This function shall only be called from the coordinator, which is the only writer of the output,
+    /// otherwise it might panic as this could violate the assumptions.
+    pub fn merge_and_publish(&self) {
+        let Some((first, others)) = self.slots.split_first() else {
+            return;
+        };
+        let mut writer = self.output.write_without_sync();
+        writer.clone_from(&first.read());
+        for slot in others {
+            writer.merge(&slot.read());
+        }
+        self.output.publish(writer);
+    }
+
+    /// Returns the buffer of the merged value, which only gets written by [`merge_and_publish()`][FanIn::merge_and_publish].
+    pub fn output(&self) -> &LeftRightBuffer<T> {
+        &self.output
+    }
+
+    /// Returns a read guard of the merged value, see [`read()`][LeftRightBuffer::read].
+    pub fn read(&self) -> ReadGuard<'_, T> {
+        self.output.read()
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct Peak(u8);
+
+    impl Merge for Peak {
+        fn merge(&mut self, contribution: &Peak) {
+            self.0 = self.0.max(contribution.0);
+        }
+    }
+
+    #[test]
+    fn the_published_contributions_get_merged() {
+        let peaks = FanIn::<Peak, 3>::new(Peak(0));
+        peaks.slot(0).expect("in range").store(Peak(4));
+        peaks.slot(2).expect("in range").store(Peak(9));
+        assert!(peaks.slot(3).is_none(), "there are only 3 producers");
+
+        let mut pending = peaks.slot(1).expect("in range").write();
+        *pending = Peak(200);
+        peaks.merge_and_publish();
+        assert_eq!(*peaks.read(), Peak(9), "pending contributions get ignored");
+
+        peaks.slot(1).expect("in range").publish(pending);
+        assert_eq!(
+            *peaks.read(),
+            Peak(9),
+            "the output only changes with a merge"
+        );
+        peaks.merge_and_publish();
+        assert_eq!(*peaks.read(), Peak(200));
+    }
+}
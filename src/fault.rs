@@ -0,0 +1,101 @@
+use crate::Violation;
+
+/// Handler of contract violations, see `set_fault_handler()` of the `fault-handler` feature.
+///
+/// It gets the name of the buffer, if one was attached with [`with_name()`][crate::LeftRightBuffer::with_name].
+/// It must not return, e.g. it resets the device after logging the violation.
+pub type FaultHandler = fn(Violation, Option<&'static str>) -> !;
+
+#[cfg(feature = "fault-handler")]
+static FAULT_HANDLER: spin::Once<FaultHandler> = spin::Once::new();
+
+/// Registers the handler, which gets invoked on contract violations instead of panicking.
+///
+/// The handler can only be registered once. Returns `false` if there was already a handler.
+#[cfg(feature = "fault-handler")]
+pub fn set_fault_handler(handler: FaultHandler) -> bool {
+    let mut registered = false;
+    FAULT_HANDLER.call_once(|| {
+        registered = true;
+        handler
+    });
+    registered
+}
+
+/// Handles the violation of the buffer called `name` by its `operation` according to the configured policy.
+///
+/// The panic message names the buffer, the operation and the side, e.g.
+/// `LRBuffer write2 (telemetry) in write(): the right side is held by a reader or another writer`,
+/// so that it explains itself in the post-mortem analysis. With the `panic-free` feature, it halts if there is no handler.
+pub(crate) fn violated(
+    violation: Violation,
+    name: Option<&'static str>,
+    operation: &'static str,
+) -> ! {
+    #[cfg(feature = "fault-handler")]
+    if let Some(handler) = FAULT_HANDLER.get() {
+        handler(violation, name)
+    }
+    #[cfg(feature = "panic-free")]
+    {
+        let _ = operation;
+        halt();
+    }
+    #[cfg(not(feature = "panic-free"))]
+    match name {
+        Some(name) => panic!(
+            "{violation} ({name}) in {operation}: {}",
+            violation.detail()
+        ),
+        None => panic!("{violation} in {operation}: {}", violation.detail()),
+    }
+}
+
+/// Handles a state, which the invariants rule out.
+///
+/// With the `panic-free` feature, it halts instead of panicking.
+pub(crate) fn unreachable(reason: &'static str) -> ! {
+    #[cfg(feature = "panic-free")]
+    {
+        let _ = reason;
+        halt();
+    }
+    #[cfg(not(feature = "panic-free"))]
+    unreachable!("{reason}")
+}
+
+/// Stops the caller for good, which is the only way to not return without the panic machinery.
+#[cfg(feature = "panic-free")]
+fn halt() -> ! {
+    loop {
+        core::hint::spin_loop();
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+#[cfg(feature = "fault-handler")]
+mod tests {
+    use super::*;
+    use crate::LeftRightBuffer;
+
+    fn handler(violation: Violation, name: Option<&'static str>) -> ! {
+        panic!("handled {violation} of {name:?}")
+    }
+
+    #[test]
+    #[should_panic(expected = "handled LRBuffer write2 of Some(\"telemetry\")")]
+    fn the_registered_handler_gets_invoked() {
+        assert!(
+            set_fault_handler(handler),
+            "no other test registers a handler"
+        );
+        assert!(
+            !set_fault_handler(handler),
+            "the handler can only be registered once"
+        );
+        let buffer = LeftRightBuffer::new(0u8).with_name("telemetry");
+        let _writer = buffer.write();
+        let _ = buffer.write();
+    }
+}
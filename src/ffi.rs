@@ -0,0 +1,100 @@
+//! C API of the `ffi` feature, so that tasks written in C can share the buffers owned by Rust.
+//!
+//! Each supported payload gets an opaque handle type and its own functions, e.g. for `u32`:
+//!
+//! ```c
+//! typedef struct LrU32 LrU32;
+//!
+//! uint32_t lr_read_u32(const LrU32 *buffer);
+//! uint32_t *lr_write_begin_u32(const LrU32 *buffer);
+//! bool lr_publish_u32(const LrU32 *buffer);
+//! ```
+//!
+//! The buffers stay owned by Rust and get shared as exported statics,
+//! which the C side declares like `extern const LrU32 SPEED;`:
+//!
+//! ```
+//! #[unsafe(no_mangle)]
+//! static SPEED: minimal_left_right::LrU32 = minimal_left_right::LrU32::new(0);
+//! ```
+//!
+//! The handles must never be null. The unwinding of a panic aborts at the boundary, see the `panic-free` feature to avoid it.
+
+use crate::LeftRightBuffer;
+
+/// Generates the handle type and the functions of a payload.
+macro_rules! ffi_buffer {
+    ($payload:ty, $handle:ident, $read:ident, $write_begin:ident, $publish:ident) => {
+        #[doc = concat!("Opaque handle of a [`LeftRightBuffer`] of `", stringify!($payload), "` for C.")]
+        pub type $handle = LeftRightBuffer<$payload>;
+
+        /// Returns a copy of the published value, see [`load()`][LeftRightBuffer::load].
+        #[unsafe(no_mangle)]
+        #[must_use]
+        pub extern "C" fn $read(buffer: &$handle) -> $payload {
+            buffer.load()
+        }
+
+        /// Returns a pointer to the pending value after syncing it like [`write()`][LeftRightBuffer::write].
+        ///
+        /// The accesses through the pointer follow the rules of [`pending_ptr()`][LeftRightBuffer::pending_ptr].
+        /// Returns a null pointer if the assumptions are violated, e.g. as the buffer got sealed.
+        #[unsafe(no_mangle)]
+        #[must_use]
+        pub extern "C" fn $write_begin(buffer: &$handle) -> *mut $payload {
+            match buffer.try_write() {
+                // the guard only syncs the pending value
+                Ok(writer) => drop(writer),
+                Err(_) => return core::ptr::null_mut(),
+            }
+            // SAFETY: taking the pointer is harmless, the accesses through it are up to the caller.
+            unsafe { buffer.pending_ptr() }
+        }
+
+        /// Publishes the value written through the pointer of the write function.
+        ///
+        /// Returns `false` if the assumptions are violated, e.g. as the buffer got sealed.
+        #[unsafe(no_mangle)]
+        pub extern "C" fn $publish(buffer: &$handle) -> bool {
+            buffer
+                .try_write_without_sync()
+                .and_then(|writer| buffer.try_publish(writer))
+                .is_ok()
+        }
+    };
+}
+
+ffi_buffer!(u8, LrU8, lr_read_u8, lr_write_begin_u8, lr_publish_u8);
+ffi_buffer!(u32, LrU32, lr_read_u32, lr_write_begin_u32, lr_publish_u32);
+ffi_buffer!(i32, LrI32, lr_read_i32, lr_write_begin_i32, lr_publish_i32);
+ffi_buffer!(f32, LrF32, lr_read_f32, lr_write_begin_f32, lr_publish_f32);
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn c_writers_publish_to_c_readers() {
+        let buffer = LrU32::new(1);
+        let pending = lr_write_begin_u32(&buffer);
+        assert!(!pending.is_null(), "the buffer accepts writers");
+        // SAFETY: this test is the only writer and publishes afterwards.
+        unsafe { *pending += 1 };
+        assert_eq!(lr_read_u32(&buffer), 1, "the value is not published yet");
+        assert!(lr_publish_u32(&buffer), "the publication succeeds");
+        assert_eq!(lr_read_u32(&buffer), 2);
+    }
+
+    #[test]
+    fn violations_get_reported_to_c() {
+        let buffer = LrF32::new(0.5);
+        buffer.seal();
+        assert!(
+            lr_write_begin_f32(&buffer).is_null(),
+            "a sealed buffer has no writer"
+        );
+        assert!(!lr_publish_f32(&buffer), "a sealed buffer never publishes");
+        assert!((lr_read_f32(&buffer) - 0.5).abs() < f32::EPSILON);
+    }
+}
@@ -0,0 +1,50 @@
+//! Memory budgets checked at compile time, see [`assert_max_size!`][crate::assert_max_size].
+
+/// Fails the build, if the size of a type exceeds the given number of bytes.
+///
+/// It documents the memory budget of a buffer next to its definition and keeps it from growing unnoticed,
+/// e.g. by a feature or a larger payload.
+///
+/// ```
+/// use minimal_left_right::{CompactBuffer, assert_max_size};
+///
+/// assert_max_size!(CompactBuffer<u32>, 12);
+/// ```
+///
+/// ```compile_fail
+/// use minimal_left_right::{CompactBuffer, assert_max_size};
+///
+/// assert_max_size!(CompactBuffer<[u8; 64]>, 64);
+/// ```
+#[macro_export]
+macro_rules! assert_max_size {
+    ($type:ty, $max:expr $(,)?) => {
+        const _: () = ::core::assert!(
+            ::core::mem::size_of::<$type>() <= $max,
+            ::core::concat!(
+                "the size of ",
+                ::core::stringify!($type),
+                " exceeds ",
+                ::core::stringify!($max),
+                " bytes"
+            )
+        );
+    };
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use crate::LeftRightBuffer;
+
+    assert_max_size!(LeftRightBuffer<[u32; 8]>, 4096);
+
+    #[test]
+    fn the_footprint_covers_both_copies_and_the_control_state() {
+        let payload = size_of::<[u32; 8]>();
+        assert!(
+            LeftRightBuffer::<[u32; 8]>::footprint() > 2 * payload,
+            "the footprint includes the control state"
+        );
+    }
+}
@@ -0,0 +1,310 @@
+//! Tearing-free double buffering of small displays, see [`FrameBufferLR`].
+
+use crate::{LeftRightBuffer, ReadGuard, WriteGuard};
+
+/// Rectangle of pixels of a [`Frame`].
+#[cfg_attr(any(test, not(feature = "fmt-free")), derive(Debug))]
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub struct Rect {
+    /// Column of the left edge.
+    pub x: usize,
+    /// Row of the top edge.
+    pub y: usize,
+    /// Number of columns.
+    pub width: usize,
+    /// Number of rows.
+    pub height: usize,
+}
+
+impl Rect {
+    /// Generates a new [`Rect`].
+    #[must_use]
+    pub const fn new(x: usize, y: usize, width: usize, height: usize) -> Rect {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Returns `true` if the rectangle covers no pixel.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    /// Returns `true` if the rectangle covers every pixel of `other`.
+    #[must_use]
+    pub const fn contains(&self, other: &Rect) -> bool {
+        self.x <= other.x
+            && self.y <= other.y
+            && other.x.saturating_add(other.width) <= self.x.saturating_add(self.width)
+            && other.y.saturating_add(other.height) <= self.y.saturating_add(self.height)
+    }
+
+    /// Returns the smallest rectangle covering both rectangles.
+    #[must_use]
+    pub fn union(&self, other: &Rect) -> Rect {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        Rect {
+            x,
+            y,
+            width: self
+                .x
+                .saturating_add(self.width)
+                .max(other.x.saturating_add(other.width))
+                - x,
+            height: self
+                .y
+                .saturating_add(self.height)
+                .max(other.y.saturating_add(other.height))
+                - y,
+        }
+    }
+
+    /// Returns the part of the rectangle within `width` columns and `height` rows.
+    fn clip(&self, width: usize, height: usize) -> Rect {
+        let x = self.x.min(width);
+        let y = self.y.min(height);
+        Rect {
+            x,
+            y,
+            width: self.width.min(width - x),
+            height: self.height.min(height - y),
+        }
+    }
+}
+
+/// Frame of `H` rows of `W` pixels of type `P`, together with the rectangles changed since the previous frame.
+#[cfg_attr(any(test, not(feature = "fmt-free")), derive(Debug))]
+#[derive(Clone, Copy)]
+pub struct Frame<const W: usize, const H: usize, P> {
+    pixels: [[P; W]; H],
+    dirty: [Rect; MAX_DIRTY_RECTS],
+    dirty_len: usize,
+    sequence: u32,
+}
+
+/// Number of dirty rectangles per [`Frame`], before further changes get merged into the last one.
+pub const MAX_DIRTY_RECTS: usize = 8;
+
+impl<const W: usize, const H: usize, P> Frame<W, H, P> {
+    /// Returns the rectangle covering the whole frame.
+    #[must_use]
+    pub const fn full() -> Rect {
+        Rect::new(0, 0, W, H)
+    }
+
+    /// Returns the rows of pixels.
+    #[must_use]
+    pub fn pixels(&self) -> &[[P; W]; H] {
+        &self.pixels
+    }
+
+    /// Returns the pixel in column `x` and row `y`, or `None` outside of the frame.
+    #[must_use]
+    pub fn pixel(&self, x: usize, y: usize) -> Option<&P> {
+        self.pixels.get(y)?.get(x)
+    }
+
+    /// Returns the rectangles changed since the previous frame.
+    #[must_use]
+    pub fn dirty_rects(&self) -> &[Rect] {
+        self.dirty.get(..self.dirty_len).unwrap_or_default()
+    }
+
+    /// Returns the number of the frame, which increases by one with every drawn frame.
+    ///
+    /// The dirty rectangles only cover the changes since the previous frame.
+    /// If the refresh skipped a frame, i.e. the sequence is not the one after the last refreshed frame,
+    /// it has to refresh the [`full()`][Frame::full] frame instead.
+    #[must_use]
+    pub const fn sequence(&self) -> u32 {
+        self.sequence
+    }
+}
+
+/// Writer of the next frame of a [`FrameBufferLR`], which records the changed rectangles.
+pub struct FrameWriter<'a, const W: usize, const H: usize, P> {
+    guard: WriteGuard<'a, Frame<W, H, P>>,
+}
+
+impl<const W: usize, const H: usize, P: Copy> FrameWriter<'_, W, H, P> {
+    /// Returns the frame drawn so far, which started as a copy of the published frame.
+    #[must_use]
+    pub fn frame(&self) -> &Frame<W, H, P> {
+        &self.guard
+    }
+
+    /// Sets the pixel in column `x` and row `y`.
+    ///
+    /// Returns `false` if the pixel is outside of the frame.
+    pub fn set_pixel(&mut self, x: usize, y: usize, pixel: P) -> bool {
+        let Some(target) = self.guard.pixels.get_mut(y).and_then(|row| row.get_mut(x)) else {
+            return false;
+        };
+        *target = pixel;
+        self.mark_dirty(Rect::new(x, y, 1, 1));
+        true
+    }
+
+    /// Fills the part of `rect` within the frame with `pixel`.
+    pub fn fill_rect(&mut self, rect: Rect, pixel: P) {
+        let rect = rect.clip(W, H);
+        for row in self.guard.pixels.iter_mut().skip(rect.y).take(rect.height) {
+            for target in row.iter_mut().skip(rect.x).take(rect.width) {
+                *target = pixel;
+            }
+        }
+        self.mark_dirty(rect);
+    }
+
+    /// Returns the rows of pixels for writing, whose changes must be recorded with [`mark_dirty()`][FrameWriter::mark_dirty].
+    pub fn pixels_mut(&mut self) -> &mut [[P; W]; H] {
+        &mut self.guard.pixels
+    }
+
+    /// Records the part of `rect` within the frame as changed.
+    ///
+    /// Rectangles covered by a recorded one are dropped.
+    /// If all [`MAX_DIRTY_RECTS`] are taken, `rect` gets merged into the last one.
+    pub fn mark_dirty(&mut self, rect: Rect) {
+        let rect = rect.clip(W, H);
+        let frame = &mut *self.guard;
+        if rect.is_empty()
+            || frame
+                .dirty_rects()
+                .iter()
+                .any(|dirty| dirty.contains(&rect))
+        {
+            return;
+        }
+        if let Some(free) = frame.dirty.get_mut(frame.dirty_len) {
+            *free = rect;
+            frame.dirty_len += 1;
+            return;
+        }
+        if let Some(last) = frame.dirty.last_mut() {
+            *last = last.union(&rect);
+        }
+    }
+}
+
+/// Buffer of display frames, whose writer draws the next frame and records the changed rectangles,
+/// so that the display refresh, e.g. an interrupt, only transfers them from the latest complete frame.
+///
+/// ```
+/// use minimal_left_right::{FrameBufferLR, Rect};
+///
+/// static DISPLAY: FrameBufferLR<8, 4, u8> = FrameBufferLR::new(0);
+///
+/// let mut frame = DISPLAY.draw();
+/// frame.fill_rect(Rect::new(2, 1, 3, 2), 0xff);
+/// DISPLAY.publish(frame);
+///
+/// let latest = DISPLAY.read_frame();
+/// assert_eq!(latest.sequence(), 1);
+/// assert!(latest.dirty_rects() == [Rect::new(2, 1, 3, 2)]);
+/// assert_eq!(latest.pixel(4, 2), Some(&0xff));
+/// ```
+pub struct FrameBufferLR<const W: usize, const H: usize, P> {
+    buffer: LeftRightBuffer<Frame<W, H, P>>,
+}
+
+impl<const W: usize, const H: usize, P: Copy> FrameBufferLR<W, H, P> {
+    /// Generates a new [`FrameBufferLR`], whose frame is filled with the `background`.
+    #[cfg(not(any(loom, shuttle)))]
+    pub const fn new(background: P) -> FrameBufferLR<W, H, P> {
+        FrameBufferLR {
+            buffer: LeftRightBuffer::new(Frame {
+                pixels: [[background; W]; H],
+                dirty: [Rect::new(0, 0, 0, 0); MAX_DIRTY_RECTS],
+                dirty_len: 0,
+                sequence: 0,
+            }),
+        }
+    }
+
+    /// Generates a new [`FrameBufferLR`], whose frame is filled with the `background`.
+    #[cfg(any(loom, shuttle))]
+    pub fn new(background: P) -> FrameBufferLR<W, H, P> {
+        FrameBufferLR {
+            buffer: LeftRightBuffer::new(Frame {
+                pixels: [[background; W]; H],
+                dirty: [Rect::new(0, 0, 0, 0); MAX_DIRTY_RECTS],
+                dirty_len: 0,
+                sequence: 0,
+            }),
+        }
+    }
+
+    /// Starts drawing the next frame on top of a copy of the published frame, without any dirty rectangles.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, otherwise it might panic as this could violate the assumptions.
+    pub fn draw(&self) -> FrameWriter<'_, W, H, P> {
+        let mut guard = self.buffer.write();
+        guard.dirty_len = 0;
+        guard.sequence = guard.sequence.wrapping_add(1);
+        FrameWriter { guard }
+    }
+
+    /// Publishes the drawn frame together with its dirty rectangles, see [`LeftRightBuffer::publish()`].
+    ///
+    /// # Panics
+    /// The writer belongs to another buffer, see [`Violation::ForeignGuard`][crate::Violation::ForeignGuard].
+    pub fn publish(&self, frame: FrameWriter<'_, W, H, P>) {
+        self.buffer.publish(frame.guard);
+    }
+
+    /// Returns a read guard of the latest complete frame.
+    #[must_use]
+    pub fn read_frame(&self) -> ReadGuard<'_, Frame<W, H, P>> {
+        self.buffer.read()
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_dirty_rects_belong_to_the_frame() {
+        let display = FrameBufferLR::<4, 3, u16>::new(0);
+        let mut frame = display.draw();
+        assert!(frame.set_pixel(3, 2, 7));
+        assert!(!frame.set_pixel(4, 0, 7), "outside of the frame");
+        frame.fill_rect(Rect::new(2, 1, 5, 5), 1);
+        frame.fill_rect(Rect::new(3, 2, 1, 1), 2);
+        assert_eq!(
+            frame.frame().dirty_rects(),
+            [Rect::new(3, 2, 1, 1), Rect::new(2, 1, 2, 2)],
+            "the rect gets clipped and covered rects get dropped"
+        );
+        display.publish(frame);
+
+        let frame = display.draw();
+        assert_eq!(frame.frame().dirty_rects(), [], "a new frame starts clean");
+        assert_eq!(frame.frame().pixel(3, 2), Some(&2), "the frame got synced");
+        display.publish(frame);
+        let latest = display.read_frame();
+        assert_eq!(latest.sequence(), 2);
+        assert_eq!(latest.pixels()[1], [0, 0, 1, 1]);
+    }
+
+    #[test]
+    fn overflowing_rects_get_merged() {
+        let display = FrameBufferLR::<16, 1, u8>::new(0);
+        let mut frame = display.draw();
+        for x in (0..16).step_by(2) {
+            assert!(frame.set_pixel(x, 0, 1));
+        }
+        assert!(frame.set_pixel(15, 0, 1));
+        let dirty = frame.frame().dirty_rects();
+        assert_eq!(dirty.len(), MAX_DIRTY_RECTS);
+        assert_eq!(dirty.last(), Some(&Rect::new(14, 0, 2, 1)));
+    }
+}
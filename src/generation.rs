@@ -0,0 +1,202 @@
+//! Publication counters, see [`Generation`] and [`Counter`].
+
+use crate::sync::{AtomicU8, AtomicU16, AtomicU32};
+use core::sync::atomic::Ordering;
+
+/// Width of the publication counter, the event flags and the publication times of a [`LeftRightBuffer`][crate::LeftRightBuffer].
+///
+/// It is implemented for `u8`, `u16` and `u32`, which is the default. Targets without 32 bit atomics
+/// pick a narrower width with [`new_with_width()`][crate::LeftRightBuffer::new_with_width]. In exchange, the window of
+/// [`Generation::is_newer_than()`] shrinks, there are fewer event flags and the ages of the publications wrap around earlier.
+pub trait Counter: Copy + Eq + Into<u32> {
+    /// The atomic type holding the counter.
+    type Atomic: Send + Sync;
+
+    /// The largest value as a `u32`, which masks the bits of the width.
+    const MASK: u32;
+
+    /// Atomic holding 0, which initializes the buffers in const contexts.
+    #[cfg(not(any(loom, shuttle)))]
+    const ZERO: Self::Atomic;
+
+    /// Returns an atomic holding 0, as the instrumented atomics of loom and shuttle cannot be created in a const context.
+    #[cfg(any(loom, shuttle))]
+    fn zero() -> Self::Atomic;
+
+    /// Returns the low bits of `value`, which fit into the width.
+    fn truncate(value: u32) -> Self;
+
+    /// Loads the value from the atomic.
+    fn load(atomic: &Self::Atomic, order: Ordering) -> Self;
+
+    /// Stores the value into the atomic.
+    fn store(atomic: &Self::Atomic, value: Self, order: Ordering);
+
+    /// Adds the value to the atomic, wrapping around on overflow, and returns the previous value.
+    fn fetch_add(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self;
+
+    /// Sets the bits of the value in the atomic and returns the previous value.
+    fn fetch_or(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self;
+
+    /// Replaces the value of the atomic and returns the previous value.
+    fn swap(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self;
+}
+
+macro_rules! impl_counter {
+    ($($t:ty => $atomic:ty),*) => {
+        $(
+            impl Counter for $t {
+                type Atomic = $atomic;
+
+                const MASK: u32 = u32::MAX >> (u32::BITS - <$t>::BITS);
+
+                #[cfg(not(any(loom, shuttle)))]
+                const ZERO: $atomic = <$atomic>::new(0);
+
+                #[cfg(any(loom, shuttle))]
+                fn zero() -> $atomic {
+                    <$atomic>::new(0)
+                }
+
+                #[inline]
+                fn truncate(value: u32) -> $t {
+                    <$t>::try_from(value & Self::MASK).unwrap_or_default()
+                }
+
+                // inlined, so that the panics of invalid orderings get ruled out at the call site
+                #[inline]
+                fn load(atomic: &$atomic, order: Ordering) -> $t {
+                    atomic.load(order)
+                }
+
+                #[inline]
+                fn store(atomic: &$atomic, value: $t, order: Ordering) {
+                    atomic.store(value, order);
+                }
+
+                #[inline]
+                fn fetch_add(atomic: &$atomic, value: $t, order: Ordering) -> $t {
+                    atomic.fetch_add(value, order)
+                }
+
+                #[inline]
+                fn fetch_or(atomic: &$atomic, value: $t, order: Ordering) -> $t {
+                    atomic.fetch_or(value, order)
+                }
+
+                #[inline]
+                fn swap(atomic: &$atomic, value: $t, order: Ordering) -> $t {
+                    atomic.swap(value, order)
+                }
+            }
+        )*
+    };
+}
+
+impl_counter!(u8 => AtomicU8, u16 => AtomicU16, u32 => AtomicU32);
+
+/// Returns an atomic holding 0 of the width `G`.
+#[cfg(not(any(loom, shuttle)))]
+pub(crate) const fn zero<G: Counter>() -> G::Atomic {
+    G::ZERO
+}
+
+/// Returns an atomic holding 0 of the width `G`.
+#[cfg(any(loom, shuttle))]
+pub(crate) fn zero<G: Counter>() -> G::Atomic {
+    G::zero()
+}
+
+/// Number of publications of a buffer, which wraps around on overflow, see [`generation()`][crate::LeftRightBuffer::generation].
+///
+/// It deliberately implements no ordering, because a raw comparison gets it backwards after the wrap around.
+/// Use [`is_newer_than()`][Generation::is_newer_than] instead, which is correct as long as both generations are
+/// less than half the range of the [`Counter`] apart, i.e. 2<sup>31</sup> publications for the default `u32`.
+///
+/// ```
+/// use minimal_left_right::Generation;
+///
+/// let before_wrap = Generation::new(u32::MAX);
+/// let after_wrap = Generation::new(2);
+/// assert!(after_wrap.is_newer_than(before_wrap));
+/// assert_eq!(after_wrap.since(before_wrap), 3);
+/// ```
+#[cfg_attr(any(test, not(feature = "fmt-free")), derive(Debug))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Generation<G = u32>(G);
+
+impl<G: Counter> Generation<G> {
+    /// Wraps the raw counter `value`.
+    #[must_use]
+    pub const fn new(value: G) -> Generation<G> {
+        Generation(value)
+    }
+
+    /// Returns the raw counter value.
+    #[must_use]
+    pub const fn get(self) -> G {
+        self.0
+    }
+
+    /// Returns `true` if `self` got published after `other`, even if the counter wrapped around in between.
+    #[must_use]
+    pub fn is_newer_than(self, other: Generation<G>) -> bool {
+        let distance: u32 = self.since(other).into();
+        distance != 0 && distance <= G::MASK >> 1
+    }
+
+    /// Returns the number of publications from `earlier` to `self`.
+    #[must_use]
+    pub fn since(self, earlier: Generation<G>) -> G {
+        G::truncate(self.0.into().wrapping_sub(earlier.0.into()))
+    }
+}
+
+impl<G: Counter> From<Generation<G>> for u32 {
+    fn from(generation: Generation<G>) -> u32 {
+        generation.0.into()
+    }
+}
+
+impl<G: Counter> PartialEq<G> for Generation<G> {
+    fn eq(&self, other: &G) -> bool {
+        self.0 == *other
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_comparison_survives_the_wrap_around() {
+        let old = Generation::new(u32::MAX - 1);
+        let new = Generation::new(1);
+        assert!(new.is_newer_than(old), "the counter wrapped around");
+        assert!(!old.is_newer_than(new));
+        assert!(
+            !new.is_newer_than(new),
+            "a generation is not newer than itself"
+        );
+        assert_eq!(new.since(old), 3);
+        assert_eq!(u32::from(new), 1);
+    }
+
+    #[test]
+    fn the_window_follows_the_width() {
+        let old = Generation::new(250u8);
+        assert!(
+            Generation::new(4u8).is_newer_than(old),
+            "the counter wrapped around"
+        );
+        assert_eq!(Generation::new(4u8).since(old), 10);
+        assert!(
+            !Generation::new(122u8).is_newer_than(old),
+            "128 publications apart exceed the window"
+        );
+        assert!(Generation::new(121u8).is_newer_than(old));
+        assert_eq!(u8::MASK, 0xff);
+        assert_eq!(u16::truncate(0x1_2345), 0x2345);
+    }
+}
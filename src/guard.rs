@@ -0,0 +1,191 @@
+#[cfg(feature = "std")]
+use crate::POISONED;
+#[cfg(feature = "stats")]
+use crate::stats::HoldTimer;
+use crate::sync::{AtomicU8, RwLockReadGuard, RwLockWriteGuard};
+use crate::{Counter, IN_SYNC, LeftRightBuffer, Side, ordering};
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+
+/// Marker keeping a write guard on the task, which took it.
+///
+/// Publishing from another task, e.g. behind an executor boundary, breaks the priority assumptions.
+pub(crate) type NotSend = PhantomData<*const ()>;
+
+/// Read guard of a [`LeftRightBuffer`].
+pub struct ReadGuard<'a, T> {
+    guard: RwLockReadGuard<'a, T>,
+    #[cfg(feature = "stats")]
+    _timer: HoldTimer<'a>,
+}
+
+impl<'a, T> ReadGuard<'a, T> {
+    #[cfg_attr(
+        not(feature = "stats"),
+        expect(unused_variables, reason = "only the stats are kept in the buffer")
+    )]
+    pub(crate) fn new<G: Counter>(
+        guard: RwLockReadGuard<'a, T>,
+        buffer: &'a LeftRightBuffer<T, G>,
+    ) -> ReadGuard<'a, T> {
+        ReadGuard {
+            guard,
+            #[cfg(feature = "stats")]
+            _timer: HoldTimer::start(&buffer.stats.read),
+        }
+    }
+}
+
+impl<T> Deref for ReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+/// Write guard of a [`LeftRightBuffer`].
+///
+/// It keeps track of whether the pending value got modified, so that the next sync copy can be skipped if it did not.
+/// It is neither `Send` nor `Sync`, so it cannot be moved to another task between the write and the publication:
+///
+/// ```compile_fail
+/// let buffer = minimal_left_right::LeftRightBuffer::new(0u8);
+/// std::thread::scope(|scope| {
+///     let writer = buffer.write();
+///     scope.spawn(|| buffer.publish(writer));
+/// });
+/// ```
+pub struct WriteGuard<'a, T> {
+    guard: RwLockWriteGuard<'a, T>,
+    state: &'a AtomicU8,
+    side: Side,
+    dirty: bool,
+    #[cfg(feature = "stats")]
+    _timer: HoldTimer<'a>,
+    _not_send: NotSend,
+}
+
+impl<'a, T> WriteGuard<'a, T> {
+    pub(crate) fn new<G: Counter>(
+        guard: RwLockWriteGuard<'a, T>,
+        buffer: &'a LeftRightBuffer<T, G>,
+        side: Side,
+    ) -> WriteGuard<'a, T> {
+        WriteGuard {
+            guard,
+            state: &buffer.state,
+            side,
+            dirty: false,
+            #[cfg(feature = "stats")]
+            _timer: HoldTimer::start(&buffer.stats.write),
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Projects the guard onto a part of the pending value, e.g. a field, to hand it to a helper function.
+    ///
+    /// The helper can modify exactly this part, but neither the rest of the value nor publish it.
+    /// The writer gets the whole guard back with [`into_inner()`][MappedWriteGuard::into_inner] for the publication.
+    ///
+    /// ```
+    /// use minimal_left_right::LeftRightBuffer;
+    ///
+    /// #[derive(Clone, Copy)]
+    /// struct Telemetry {
+    ///     speed: u16,
+    ///     temperature: i8,
+    /// }
+    ///
+    /// fn measure_speed(speed: &mut u16) {
+    ///     *speed = 1200;
+    /// }
+    ///
+    /// let buffer = LeftRightBuffer::new(Telemetry { speed: 0, temperature: 20 });
+    /// let mut speed = buffer.write().map(|telemetry| &mut telemetry.speed);
+    /// measure_speed(&mut speed);
+    /// buffer.publish(speed.into_inner());
+    /// assert_eq!(buffer.read().speed, 1200);
+    /// ```
+    pub fn map<U: ?Sized>(
+        mut self,
+        project: impl FnOnce(&mut T) -> &mut U,
+    ) -> MappedWriteGuard<'a, T, U> {
+        let part: *mut U = project(&mut self.guard);
+        MappedWriteGuard { writer: self, part }
+    }
+}
+
+impl<T> WriteGuard<'_, T> {
+    /// Returns `true` if the guard was taken from `side` of `buffer`.
+    pub(crate) fn belongs_to<G: Counter>(
+        &self,
+        buffer: &LeftRightBuffer<T, G>,
+        side: Side,
+    ) -> bool {
+        let state: &AtomicU8 = &buffer.state;
+        core::ptr::eq(self.state, state) && self.side == side
+    }
+}
+
+impl<T> Deref for WriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<T> DerefMut for WriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.dirty = true;
+        &mut self.guard
+    }
+}
+
+impl<T> Drop for WriteGuard<'_, T> {
+    fn drop(&mut self) {
+        #[cfg(feature = "std")]
+        if std::thread::panicking() {
+            self.state.fetch_or(POISONED, ordering::RELEASE);
+        }
+        if self.dirty {
+            // the pending side diverged from the published side
+            self.state.fetch_and(!IN_SYNC, ordering::RELAXED);
+        }
+    }
+}
+
+/// Write guard projected onto a part of the pending value, see [`WriteGuard::map()`].
+///
+/// Like the [`WriteGuard`], it is neither `Send` nor `Sync`. Dropping it keeps the modifications pending.
+pub struct MappedWriteGuard<'a, T, U: ?Sized> {
+    writer: WriteGuard<'a, T>,
+    // points into the pending value, which stays locked by the writer
+    part: *mut U,
+}
+
+impl<'a, T, U: ?Sized> MappedWriteGuard<'a, T, U> {
+    /// Returns the write guard of the whole pending value, e.g. to publish it.
+    #[must_use]
+    pub fn into_inner(self) -> WriteGuard<'a, T> {
+        self.writer
+    }
+}
+
+impl<T, U: ?Sized> Deref for MappedWriteGuard<'_, T, U> {
+    type Target = U;
+
+    fn deref(&self) -> &U {
+        // SAFETY: the part belongs to the pending value, which the writer keeps locked as long as the guard lives.
+        unsafe { &*self.part }
+    }
+}
+
+impl<T, U: ?Sized> DerefMut for MappedWriteGuard<'_, T, U> {
+    fn deref_mut(&mut self) -> &mut U {
+        self.writer.dirty = true;
+        // SAFETY: the part belongs to the pending value, which the writer keeps locked as long as the guard lives.
+        unsafe { &mut *self.part }
+    }
+}
@@ -0,0 +1,296 @@
+//! Hazard-pointer reader tracking of the `strict-ordering` feature, see [`HazardBuffer`].
+
+use crate::sync::{AtomicU8, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use crate::{Error, Violation, fault, ordering};
+use core::ops::{Deref, DerefMut};
+
+/// Number of slots: the published one, one pinned by slow readers and one for the writer.
+const SLOTS: u8 = 3;
+/// Hazard of a reader, which is not registered.
+const FREE: u8 = u8::MAX;
+/// Hazard of a registered reader, which does not read at the moment.
+const IDLE: u8 = u8::MAX - 1;
+
+/// Buffer for SMP systems, whose readers announce the slot they read in hazard pointers instead of per-side reader counters.
+///
+/// The value lives in three slots. Every registered reader owns one of the `READERS` hazard pointers,
+/// where it announces the slot it reads. The writer writes into a slot, which is neither published nor announced.
+/// So a single slow reader pins exactly one retired slot and never stalls the writer,
+/// while the readers of a [`LeftRightBuffer`][crate::LeftRightBuffer] block the writer until they release the retired side.
+///
+/// It is a separate type with its own API rather than a tracking scheme of a [`LeftRightBuffer`][crate::LeftRightBuffer],
+/// as the readers register for a hazard pointer first and the writer may find no free slot.
+///
+/// ```
+/// use minimal_left_right::HazardBuffer;
+///
+/// let setpoint = HazardBuffer::<u32, 2>::new(0);
+/// let mut reader = setpoint.reader().expect("a hazard pointer is free");
+/// let slow = reader.read();
+///
+/// let mut writer = setpoint.write().expect("the slow reader pins only one slot");
+/// *writer = 1;
+/// setpoint.publish(writer);
+/// let mut writer = setpoint.write().expect("the slow reader pins only one slot");
+/// *writer = 2;
+/// setpoint.publish(writer);
+/// assert_eq!(*slow, 0);
+/// drop(slow);
+/// assert_eq!(*reader.read(), 2);
+/// ```
+pub struct HazardBuffer<T, const READERS: usize> {
+    slots: [RwLock<T>; SLOTS as usize],
+    published: AtomicU8,
+    hazards: [AtomicU8; READERS],
+}
+
+/// Registered reader of a [`HazardBuffer`], which owns one of its hazard pointers, see [`HazardBuffer::reader()`].
+pub struct HazardReader<'a, T, const READERS: usize> {
+    buffer: &'a HazardBuffer<T, READERS>,
+    hazard: &'a AtomicU8,
+}
+
+/// Read guard of the published value of a [`HazardBuffer`], see [`HazardReader::read()`].
+pub struct HazardReadGuard<'a, T> {
+    guard: RwLockReadGuard<'a, T>,
+    hazard: &'a AtomicU8,
+}
+
+/// Write guard of a free slot of a [`HazardBuffer`], see [`HazardBuffer::write()`].
+pub struct HazardWriteGuard<'a, T> {
+    guard: RwLockWriteGuard<'a, T>,
+    // the slots of the buffer, which identify the buffer of the guard
+    slots: &'a [RwLock<T>; SLOTS as usize],
+    slot: u8,
+}
+
+impl<T: Clone, const READERS: usize> HazardBuffer<T, READERS> {
+    /// Generates a new [`HazardBuffer`] for up to `READERS` registered readers.
+    ///
+    /// It does not compile with more than 253 readers.
+    pub fn new(data: T) -> HazardBuffer<T, READERS> {
+        const {
+            assert!(
+                READERS < IDLE as usize,
+                "a HazardBuffer has at most 253 readers"
+            );
+        };
+        HazardBuffer {
+            slots: [
+                RwLock::new(data.clone()),
+                RwLock::new(data.clone()),
+                RwLock::new(data),
+            ],
+            published: AtomicU8::new(0),
+            hazards: core::array::from_fn(|_| AtomicU8::new(FREE)),
+        }
+    }
+
+    /// Registers a reader, which owns a hazard pointer until it gets dropped.
+    ///
+    /// Returns `None` if all `READERS` hazard pointers are taken.
+    #[must_use]
+    pub fn reader(&self) -> Option<HazardReader<'_, T, READERS>> {
+        let hazard = self.hazards.iter().find(|hazard| {
+            hazard
+                .compare_exchange(FREE, IDLE, ordering::ACQ_REL, ordering::RELAXED)
+                .is_ok()
+        })?;
+        Some(HazardReader {
+            buffer: self,
+            hazard,
+        })
+    }
+
+    /// Returns a write guard of a slot, which is neither published nor announced by a reader.
+    /// It starts with the published value.
+    ///
+    /// Returns `None` if the readers announced both retired slots.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, otherwise it might panic as this could violate the assumptions.
+    #[must_use]
+    pub fn write(&self) -> Option<HazardWriteGuard<'_, T>> {
+        let published = self.published.load(ordering::ACQUIRE);
+        let mut writer = (0..SLOTS)
+            .filter(|&slot| slot != published && !self.is_announced(slot))
+            .find_map(|slot| {
+                Some(HazardWriteGuard {
+                    guard: self.slots.get(usize::from(slot))?.try_write()?,
+                    slots: &self.slots,
+                    slot,
+                })
+            })?;
+        if let Some(current) = self.slots.get(usize::from(published)) {
+            writer.clone_from(&current.read());
+        }
+        Some(writer)
+    }
+
+    /// Publishes the written value, which the readers see with their next [`read()`][HazardReader::read].
+    ///
+    /// # Panics
+    /// The guard belongs to another buffer, see [`Violation::ForeignGuard`].
+    pub fn publish(&self, writer: HazardWriteGuard<'_, T>) {
+        if let Err(Error::Violation(violation)) = self.try_publish(writer) {
+            fault::violated(violation, None, "publish()");
+        }
+    }
+
+    /// Publishes the written value like [`publish()`][HazardBuffer::publish], but returns an error instead of handling the contract violation.
+    ///
+    /// # Errors
+    /// [`Error::Violation`] if the guard belongs to another buffer. The guard gets dropped without publishing its value.
+    pub fn try_publish(&self, writer: HazardWriteGuard<'_, T>) -> Result<(), Error> {
+        if !core::ptr::eq(writer.slots, &raw const self.slots) {
+            return Err(Error::Violation(Violation::ForeignGuard));
+        }
+        let slot = writer.slot;
+        drop(writer);
+        self.published.store(slot, ordering::RELEASE);
+        Ok(())
+    }
+
+    /// Returns the number of slots, which the readers announced, but which are not published anymore.
+    ///
+    /// The writer only fails to get a slot, if it is 2.
+    #[must_use]
+    pub fn pinned(&self) -> usize {
+        let published = self.published.load(ordering::ACQUIRE);
+        (0..SLOTS)
+            .filter(|&slot| slot != published && self.is_announced(slot))
+            .count()
+    }
+
+    /// Returns `true` if a reader announced `slot`.
+    fn is_announced(&self, slot: u8) -> bool {
+        self.hazards
+            .iter()
+            .any(|hazard| hazard.load(ordering::ACQUIRE) == slot)
+    }
+}
+
+impl<T, const READERS: usize> HazardReader<'_, T, READERS> {
+    /// Returns a read guard of the published value.
+    ///
+    /// The reader announces the published slot and checks that it is still published afterwards,
+    /// so the writer either sees the announcement or the reader retries with the newer slot.
+    #[must_use]
+    pub fn read(&mut self) -> HazardReadGuard<'_, T> {
+        loop {
+            let slot = self.buffer.published.load(ordering::ACQUIRE);
+            self.hazard.store(slot, ordering::RELEASE);
+            if self.buffer.published.load(ordering::ACQUIRE) == slot
+                && let Some(guard) = self
+                    .buffer
+                    .slots
+                    .get(usize::from(slot))
+                    .and_then(RwLock::try_read)
+            {
+                return HazardReadGuard {
+                    guard,
+                    hazard: self.hazard,
+                };
+            }
+            core::hint::spin_loop();
+        }
+    }
+}
+
+impl<T, const READERS: usize> Drop for HazardReader<'_, T, READERS> {
+    fn drop(&mut self) {
+        self.hazard.store(FREE, ordering::RELEASE);
+    }
+}
+
+impl<T> Deref for HazardReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<T> Drop for HazardReadGuard<'_, T> {
+    fn drop(&mut self) {
+        self.hazard.store(IDLE, ordering::RELEASE);
+    }
+}
+
+impl<T> Deref for HazardWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<T> DerefMut for HazardWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.guard
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_slow_reader_pins_one_slot() {
+        let buffer = HazardBuffer::<u8, 2>::new(0);
+        let mut slow = buffer.reader().expect("a hazard pointer is free");
+        let mut fast = buffer.reader().expect("a hazard pointer is free");
+        assert!(buffer.reader().is_none(), "all hazard pointers are taken");
+
+        let held = slow.read();
+        for value in 1..=3 {
+            let mut writer = buffer.write().expect("one slot stays free");
+            assert_eq!(
+                *writer,
+                value - 1,
+                "the writer starts with the published value"
+            );
+            *writer = value;
+            buffer.publish(writer);
+        }
+        assert_eq!(*held, 0);
+        assert_eq!(buffer.pinned(), 1);
+
+        let pinning = fast.read();
+        let mut writer = buffer
+            .write()
+            .expect("the fast reader reads the published slot");
+        *writer = 4;
+        buffer.publish(writer);
+        assert_eq!(buffer.pinned(), 2);
+        assert!(buffer.write().is_none(), "both retired slots are pinned");
+
+        drop(held);
+        drop(slow);
+        assert!(buffer.reader().is_some(), "the hazard pointer got released");
+        drop(pinning);
+        assert_eq!(buffer.pinned(), 0);
+        assert_eq!(*fast.read(), 4);
+    }
+
+    #[test]
+    fn foreign_guards_get_rejected() {
+        let buffer = HazardBuffer::<u8, 1>::new(0);
+        let other = HazardBuffer::<u8, 1>::new(0);
+        let mut writer = other.write().expect("a slot is free");
+        *writer = 1;
+        assert_eq!(
+            buffer.try_publish(writer),
+            Err(Error::Violation(Violation::ForeignGuard))
+        );
+        let mut reader = other.reader().expect("a hazard pointer is free");
+        assert_eq!(*reader.read(), 0, "nothing got published");
+
+        let mut writer = buffer.write().expect("a slot is free");
+        *writer = 2;
+        assert_eq!(buffer.try_publish(writer), Ok(()));
+        let mut reader = buffer.reader().expect("a hazard pointer is free");
+        assert_eq!(*reader.read(), 2);
+    }
+}
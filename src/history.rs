@@ -0,0 +1,377 @@
+//! Buffers keeping the last publications of the `history` feature, see [`LeftRightHistory`].
+
+use crate::{LeftRightBuffer, clock};
+
+/// A published value of a [`LeftRightHistory`] with the time of its publication.
+#[cfg_attr(any(test, not(feature = "fmt-free")), derive(Debug))]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct HistoryEntry<T> {
+    /// The published value.
+    pub value: T,
+    /// The time in ticks of the registered [`Clock`][crate::Clock], when the value got published. `None` without a clock.
+    pub published_at: Option<u32>,
+}
+
+/// Ring of the last `N` entries, which is the payload of the buffer.
+#[derive(Clone)]
+struct Ring<T, const N: usize> {
+    entries: [HistoryEntry<T>; N],
+    // The index of the newest entry.
+    newest: usize,
+    len: usize,
+}
+
+impl<T, const N: usize> Ring<T, N> {
+    fn push(&mut self, entry: HistoryEntry<T>) {
+        self.newest = (self.newest + 1) % N;
+        self.entries[self.newest] = entry;
+        self.len = (self.len + 1).min(N);
+    }
+
+    /// Returns the entry `back` publications before the newest one.
+    fn get(&self, back: usize) -> Option<&HistoryEntry<T>> {
+        (back < self.len).then(|| &self.entries[(self.newest + N - back) % N])
+    }
+}
+
+/// Buffer, which keeps the last `N` published values with their publication times.
+///
+/// All entries get published together, so the readers always see a consistent history,
+/// e.g. the newest two samples for an interpolation without a publication racing between two reads.
+/// As the sync copies all entries, keep `N` and the payload small.
+pub struct LeftRightHistory<T, const N: usize> {
+    buffer: LeftRightBuffer<Ring<T, N>>,
+}
+
+impl<T: Clone, const N: usize> LeftRightHistory<T, N> {
+    /// Generates a new [`LeftRightHistory`], whose history starts with the initial value.
+    ///
+    /// It does not compile with `N` being 0.
+    pub fn new(data: T) -> LeftRightHistory<T, N> {
+        const { assert!(N > 0, "the history keeps at least one entry") };
+        let entry = HistoryEntry {
+            value: data,
+            published_at: clock::now(),
+        };
+        LeftRightHistory {
+            buffer: LeftRightBuffer::new_cloned(Ring {
+                entries: core::array::from_fn(|_| entry.clone()),
+                newest: 0,
+                len: 1,
+            }),
+        }
+    }
+
+    /// Publishes `data` as the newest entry, which replaces the oldest one if the history is full.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, otherwise it might panic as this could violate the assumptions.
+    pub fn publish(&self, data: T) {
+        let mut ring = self.buffer.write();
+        ring.push(HistoryEntry {
+            value: data,
+            published_at: clock::now(),
+        });
+        self.buffer.publish(ring);
+    }
+
+    /// Returns a copy of the newest entry.
+    #[must_use]
+    pub fn latest(&self) -> HistoryEntry<T> {
+        let ring = self.buffer.read();
+        ring.entries[ring.newest].clone()
+    }
+
+    /// Returns copies of the newest and the previous entry from the same publication, e.g. to interpolate between them.
+    ///
+    /// Returns `None` as long as there is only the initial value.
+    #[must_use]
+    pub fn read_last_two(&self) -> Option<(HistoryEntry<T>, HistoryEntry<T>)> {
+        let ring = self.buffer.read();
+        Some((ring.get(0)?.clone(), ring.get(1)?.clone()))
+    }
+
+    /// Returns the number of entries in the history, which is at most `N`.
+    #[must_use]
+    pub fn entry_count(&self) -> usize {
+        self.buffer.read().len
+    }
+
+    /// Copies the values of the history into `out`, oldest first, and clears the history, e.g. to dump the recent state into a crash log.
+    ///
+    /// If `out` is too short, only the newest values get copied. The newest value stays published as the only entry.
+    /// The copy and the clearing happen in one write, so no publication gets lost in between.
+    /// Returns the number of copied values.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, otherwise it might panic as this could violate the assumptions.
+    pub fn drain_history(&self, out: &mut [T]) -> usize {
+        let mut ring = self.buffer.write();
+        let count = ring.len.min(out.len());
+        for (slot, back) in out.iter_mut().zip((0..count).rev()) {
+            if let Some(entry) = ring.get(back) {
+                slot.clone_from(&entry.value);
+            }
+        }
+        ring.len = 1;
+        self.buffer.publish(ring);
+        count
+    }
+}
+
+/// Payloads, whose older values can be stored as compact deltas, see [`LeftRightDeltaHistory`].
+///
+/// It complements `Diff` of the `diff` feature, whose bit mask tells which fields changed, but not how to restore them.
+///
+/// ```
+/// use minimal_left_right::Delta;
+///
+/// #[derive(Clone)]
+/// struct Frame {
+///     samples: [u16; 512],
+/// }
+///
+/// impl Delta for Frame {
+///     // the only sample, which changed, with its older value
+///     type Delta = (usize, u16);
+///
+///     fn delta_to(&self, older: &Frame) -> (usize, u16) {
+///         let index = (0..512).find(|&index| self.samples[index] != older.samples[index]).unwrap_or(0);
+///         (index, older.samples[index])
+///     }
+///
+///     fn apply(&mut self, &(index, sample): &(usize, u16)) {
+///         self.samples[index] = sample;
+///     }
+/// }
+/// ```
+pub trait Delta {
+    /// The difference between two values.
+    type Delta: Clone;
+
+    /// Returns the delta, which turns `self` back into the `older` value.
+    fn delta_to(&self, older: &Self) -> Self::Delta;
+
+    /// Turns the value back into the older one, from which the `delta` got taken with [`delta_to()`][Delta::delta_to].
+    fn apply(&mut self, delta: &Self::Delta);
+}
+
+/// Newest value and the deltas back to the `N` older values, which is the payload of the buffer.
+struct DeltaRing<T: Delta, const N: usize> {
+    newest: HistoryEntry<T>,
+    // The deltas to the older values with their publication times.
+    older: [Option<(T::Delta, Option<u32>)>; N],
+    // The index of the delta to the previous value.
+    previous: usize,
+    len: usize,
+}
+
+impl<T: Delta + Clone, const N: usize> Clone for DeltaRing<T, N> {
+    fn clone(&self) -> Self {
+        DeltaRing {
+            newest: self.newest.clone(),
+            older: self.older.clone(),
+            previous: self.previous,
+            len: self.len,
+        }
+    }
+}
+
+impl<T: Delta + Clone, const N: usize> DeltaRing<T, N> {
+    fn push(&mut self, entry: HistoryEntry<T>) {
+        let delta = entry.value.delta_to(&self.newest.value);
+        let older = core::mem::replace(&mut self.newest, entry);
+        self.previous = (self.previous + 1) % N;
+        self.older[self.previous] = Some((delta, older.published_at));
+        self.len = (self.len + 1).min(N);
+    }
+
+    /// Reconstructs the entry `back` publications before the newest one.
+    fn get(&self, back: usize) -> Option<HistoryEntry<T>> {
+        if back > self.len {
+            return None;
+        }
+        let mut entry = self.newest.clone();
+        for step in 0..back {
+            let (delta, published_at) = self.older[(self.previous + N - step) % N].as_ref()?;
+            entry.value.apply(delta);
+            entry.published_at = *published_at;
+        }
+        Some(entry)
+    }
+}
+
+/// Buffer like [`LeftRightHistory`], which stores the `N` older values as deltas instead of full copies.
+///
+/// The newest value gets read without any reconstruction. The older values get reconstructed on read
+/// by applying the deltas to the newest value, which suits rarely read post-mortem logs of large payloads.
+pub struct LeftRightDeltaHistory<T: Delta, const N: usize> {
+    buffer: LeftRightBuffer<DeltaRing<T, N>>,
+}
+
+impl<T: Delta + Clone, const N: usize> LeftRightDeltaHistory<T, N> {
+    /// Generates a new [`LeftRightDeltaHistory`], whose history starts with the initial value.
+    ///
+    /// It does not compile with `N` being 0.
+    pub fn new(data: T) -> LeftRightDeltaHistory<T, N> {
+        const { assert!(N > 0, "the history keeps at least one older entry") };
+        LeftRightDeltaHistory {
+            buffer: LeftRightBuffer::new_cloned(DeltaRing {
+                newest: HistoryEntry {
+                    value: data,
+                    published_at: clock::now(),
+                },
+                older: core::array::from_fn(|_| None),
+                previous: 0,
+                len: 0,
+            }),
+        }
+    }
+
+    /// Publishes `data` as the newest entry and keeps the delta to the previous one, which replaces the oldest delta if the history is full.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, otherwise it might panic as this could violate the assumptions.
+    pub fn publish(&self, data: T) {
+        let mut ring = self.buffer.write();
+        ring.push(HistoryEntry {
+            value: data,
+            published_at: clock::now(),
+        });
+        self.buffer.publish(ring);
+    }
+
+    /// Returns a copy of the newest entry.
+    #[must_use]
+    pub fn latest(&self) -> HistoryEntry<T> {
+        self.buffer.read().newest.clone()
+    }
+
+    /// Returns the entry `back` publications before the newest one, reconstructed from the deltas.
+    ///
+    /// Returns `None` if the history does not reach back that far.
+    #[must_use]
+    pub fn entry(&self, back: usize) -> Option<HistoryEntry<T>> {
+        self.buffer.read().get(back)
+    }
+
+    /// Returns the newest and the previous entry from the same publication, see [`LeftRightHistory::read_last_two()`].
+    #[must_use]
+    pub fn read_last_two(&self) -> Option<(HistoryEntry<T>, HistoryEntry<T>)> {
+        let ring = self.buffer.read();
+        Some((ring.newest.clone(), ring.get(1)?))
+    }
+
+    /// Returns the number of entries in the history including the newest one, which is at most `N + 1`.
+    #[must_use]
+    pub fn entry_count(&self) -> usize {
+        self.buffer.read().len + 1
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+    use crate::clock::test_clock;
+
+    #[test]
+    fn the_last_two_entries_come_from_one_publication() {
+        test_clock::register();
+        let position = LeftRightHistory::<i32, 3>::new(0);
+        let start = position
+            .latest()
+            .published_at
+            .expect("a clock is registered");
+        assert_eq!(
+            position.read_last_two(),
+            None,
+            "there is only the initial value"
+        );
+
+        test_clock::advance(10);
+        position.publish(5);
+        test_clock::advance(10);
+        position.publish(8);
+        let (newest, previous) = position.read_last_two().expect("two values got published");
+        assert_eq!(newest.value, 8);
+        assert_eq!(newest.published_at, Some(start.wrapping_add(20)));
+        assert_eq!(previous.value, 5);
+        assert_eq!(previous.published_at, Some(start.wrapping_add(10)));
+    }
+
+    #[test]
+    fn the_oldest_entry_gets_replaced() {
+        let history = LeftRightHistory::<u8, 2>::new(1);
+        assert_eq!(history.entry_count(), 1);
+        for value in 2..=4 {
+            history.publish(value);
+        }
+        assert_eq!(history.entry_count(), 2, "the history is full");
+        let (newest, previous) = history.read_last_two().expect("the history is full");
+        assert_eq!((newest.value, previous.value), (4, 3));
+        assert_eq!(history.latest().value, 4);
+    }
+
+    #[test]
+    fn draining_copies_and_clears_the_history() {
+        let history = LeftRightHistory::<u8, 4>::new(1);
+        for value in 2..=3 {
+            history.publish(value);
+        }
+        let mut log = [0; 2];
+        assert_eq!(history.drain_history(&mut log), 2);
+        assert_eq!(log, [2, 3], "the newest values got copied");
+        assert_eq!(history.entry_count(), 1, "only the newest value stays");
+        assert_eq!(history.latest().value, 3);
+
+        history.publish(4);
+        let mut log = [0; 4];
+        assert_eq!(history.drain_history(&mut log), 2);
+        assert_eq!(log[..2], [3, 4]);
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Odometer(u32);
+
+    impl Delta for Odometer {
+        type Delta = u16;
+
+        fn delta_to(&self, older: &Odometer) -> u16 {
+            u16::try_from(self.0 - older.0).expect("small steps")
+        }
+
+        fn apply(&mut self, delta: &u16) {
+            self.0 -= u32::from(*delta);
+        }
+    }
+
+    #[test]
+    fn the_older_values_get_reconstructed_from_the_deltas() {
+        test_clock::register();
+        let odometer = LeftRightDeltaHistory::<Odometer, 2>::new(Odometer(100));
+        let start = odometer
+            .latest()
+            .published_at
+            .expect("a clock is registered");
+        assert_eq!(odometer.read_last_two(), None);
+        for distance in [110, 125, 130] {
+            test_clock::advance(10);
+            odometer.publish(Odometer(distance));
+        }
+
+        assert_eq!(odometer.entry_count(), 3, "the history is full");
+        assert_eq!(odometer.latest().value, Odometer(130));
+        let oldest = odometer
+            .entry(2)
+            .expect("the history reaches back 2 entries");
+        assert_eq!(oldest.value, Odometer(110));
+        assert_eq!(oldest.published_at, Some(start.wrapping_add(10)));
+        assert_eq!(odometer.entry(3), None, "the initial value got replaced");
+        let (newest, previous) = odometer.read_last_two().expect("two values got published");
+        assert_eq!(
+            (newest.value, previous.value),
+            (Odometer(130), Odometer(125))
+        );
+    }
+}
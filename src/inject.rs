@@ -0,0 +1,62 @@
+//! Fault injection of the `test-util` feature, see `inject_reader_hold()` and `force_fallback_path()` of [`LeftRightBuffer`][crate::LeftRightBuffer].
+//!
+//! They trigger the rare code paths deliberately, which otherwise only occur if a reader and the writer race each other.
+
+use crate::sync::RwLockWriteGuard;
+
+/// Guard of an injected fault, which gets removed on drop.
+///
+/// See [`force_fallback_path()`][crate::LeftRightBuffer::force_fallback_path].
+pub struct InjectedFault<'a, T> {
+    pub(crate) _guard: RwLockWriteGuard<'a, T>,
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use crate::{Error, LeftRightBuffer, Side, Violation};
+
+    #[test]
+    fn a_held_reader_blocks_the_sync() {
+        let buffer = LeftRightBuffer::new(0u8);
+        let mut writer = buffer.write();
+        *writer = 1;
+        buffer.publish(writer);
+
+        let reader = buffer.inject_reader_hold();
+        assert_eq!(*reader, 0);
+        assert_eq!(
+            buffer.try_write().err(),
+            Some(Error::Violation(Violation::SyncWrite(Side::Left)))
+        );
+        drop(reader);
+        assert_eq!(*buffer.write(), 1);
+    }
+
+    #[test]
+    fn readers_take_the_fallback_path() {
+        let buffer = LeftRightBuffer::new(0u8);
+        buffer.store(1);
+
+        let fault = buffer.force_fallback_path();
+        assert_eq!(*buffer.read(), 0);
+        assert!(buffer.try_read().is_none(), "the published side is held");
+        assert_eq!(buffer.read_bounded(3).map(|thing| *thing).ok(), Some(0));
+        drop(fault);
+        assert_eq!(*buffer.read(), 1);
+    }
+
+    #[test]
+    #[cfg(not(feature = "fault-handler"))] // a registered handler formats the violation itself
+    #[should_panic(
+        expected = "LRBuffer read in read(): both sides are held by writers, so there is no value to read"
+    )]
+    fn the_reader_never_waits_for_a_writer() {
+        let buffer = LeftRightBuffer::new(0u8);
+        buffer.store(1);
+
+        let _fault = buffer.force_fallback_path();
+        let _writer = buffer.write_without_sync();
+        let _ = buffer.read();
+    }
+}
@@ -0,0 +1,99 @@
+//! Read leases of the `read-lease` feature, see [`read_leased()`][crate::LeftRightBuffer::read_leased].
+
+use crate::{ReadGuard, clock};
+use core::ops::Deref;
+
+/// Hook invoked with the hold time in ticks, if a [`ReadLease`] was held longer than its bound.
+pub type LeaseHook = fn(held: u32);
+
+static LEASE_HOOK: spin::Once<LeaseHook> = spin::Once::new();
+
+/// Registers the hook, which gets invoked if a [`ReadLease`] was held longer than its bound.
+///
+/// The hook can only be registered once. Returns `false` if there was already a hook.
+pub fn set_lease_hook(hook: LeaseHook) -> bool {
+    let mut registered = false;
+    LEASE_HOOK.call_once(|| {
+        registered = true;
+        hook
+    });
+    registered
+}
+
+/// Read guard, which reports on drop if it was held longer than its bound.
+///
+/// The time gets measured with the registered [`Clock`][crate::Clock]. Without a clock, nothing gets reported.
+pub struct ReadLease<'a, T> {
+    guard: ReadGuard<'a, T>,
+    acquired: Option<u32>,
+    max_hold: u32,
+}
+
+impl<'a, T> ReadLease<'a, T> {
+    pub(crate) fn new(guard: ReadGuard<'a, T>, max_hold: u32) -> ReadLease<'a, T> {
+        ReadLease {
+            guard,
+            acquired: clock::now(),
+            max_hold,
+        }
+    }
+}
+
+impl<T> Deref for ReadLease<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<T> Drop for ReadLease<'_, T> {
+    fn drop(&mut self) {
+        if let (Some(acquired), Some(now), Some(hook)) =
+            (self.acquired, clock::now(), LEASE_HOOK.get())
+        {
+            let held = now.wrapping_sub(acquired);
+            if held > self.max_hold {
+                hook(held);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+    use crate::LeftRightBuffer;
+    use crate::clock::test_clock;
+    use core::cell::Cell;
+
+    std::thread_local! {
+        static OVERRUN: Cell<Option<u32>> = const { Cell::new(None) };
+    }
+
+    fn record_overrun(held: u32) {
+        OVERRUN.with(|overrun| overrun.set(Some(held)));
+    }
+
+    #[test]
+    fn long_held_leases_get_reported() {
+        test_clock::register();
+        assert!(
+            set_lease_hook(record_overrun),
+            "no other test registers a hook"
+        );
+        let buffer = LeftRightBuffer::new(1u8);
+
+        let lease = buffer.read_leased(10);
+        test_clock::advance(10);
+        assert_eq!(*lease, 1);
+        drop(lease);
+        assert_eq!(OVERRUN.with(Cell::get), None);
+
+        let lease = buffer.read_leased(10);
+        test_clock::advance(11);
+        drop(lease);
+        assert_eq!(OVERRUN.with(Cell::get), Some(11));
+    }
+}
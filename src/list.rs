@@ -0,0 +1,62 @@
+use crate::LeftRightBuffer;
+use heapless::Vec;
+
+impl<T: Clone, const N: usize> LeftRightBuffer<Vec<T, N>> {
+    /// Pushes `item` onto the pending list without publishing it.
+    ///
+    /// Several edits get published at once, e.g. with `buffer.publish(buffer.write())`, as the write only syncs after a publication.
+    /// So the first edit after a publication starts from the published list.
+    ///
+    /// # Errors
+    /// Returns `item` back if the list is full.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, see [`write()`][LeftRightBuffer::write].
+    pub fn push_pending(&self, item: T) -> Result<(), T> {
+        self.write().push(item)
+    }
+
+    /// Removes all items of the pending list without publishing it.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, see [`write()`][LeftRightBuffer::write].
+    pub fn clear_pending(&self) {
+        self.write().clear();
+    }
+
+    /// Shortens the pending list to `len` items without publishing it.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, see [`write()`][LeftRightBuffer::write].
+    pub fn truncate_pending(&self, len: usize) {
+        self.write().truncate(len);
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use crate::LeftRightBuffer;
+    use heapless::Vec;
+
+    #[test]
+    fn list_edits_get_published_together() {
+        let alarms = LeftRightBuffer::new_cloned(Vec::<u16, 3>::new());
+        assert_eq!(alarms.push_pending(7), Ok(()));
+        assert_eq!(alarms.push_pending(8), Ok(()));
+        assert!(alarms.read().is_empty(), "the edits are pending");
+        alarms.publish(alarms.write());
+        assert_eq!(alarms.read().as_slice(), [7, 8]);
+        assert_eq!(alarms.generation(), 1, "both pushes got published at once");
+
+        assert_eq!(alarms.push_pending(9), Ok(()));
+        assert_eq!(alarms.push_pending(10), Err(10));
+        alarms.truncate_pending(1);
+        alarms.publish(alarms.write());
+        assert_eq!(alarms.read().as_slice(), [7]);
+
+        alarms.clear_pending();
+        alarms.publish(alarms.write());
+        assert!(alarms.read().is_empty(), "the list got cleared");
+    }
+}
@@ -0,0 +1,110 @@
+//! Keyed registries of the `heapless` feature, see [`LeftRightMap`].
+
+use crate::{LeftRightBuffer, ReadGuard};
+use heapless::LinearMap;
+
+/// Buffer of a map of up to `N` entries, e.g. the health per node or the limits per axis.
+///
+/// The writer updates single entries with [`insert()`][LeftRightMap::insert] and [`remove()`][LeftRightMap::remove],
+/// which stay invisible until [`publish()`][LeftRightMap::publish]. The pending map starts with the published map,
+/// so the readers always see a consistent snapshot of all entries.
+///
+/// ```
+/// use minimal_left_right::LeftRightMap;
+///
+/// let health = LeftRightMap::<u8, bool, 4>::new();
+/// health.insert(1, true).expect("there is space");
+/// health.insert(2, false).expect("there is space");
+/// health.publish();
+/// assert_eq!(health.get(&2), Some(false));
+/// ```
+pub struct LeftRightMap<K, V, const N: usize> {
+    buffer: LeftRightBuffer<LinearMap<K, V, N>>,
+}
+
+impl<K: Clone + Eq, V: Clone, const N: usize> LeftRightMap<K, V, N> {
+    /// Generates a new empty [`LeftRightMap`].
+    #[must_use]
+    pub fn new() -> LeftRightMap<K, V, N> {
+        LeftRightMap {
+            buffer: LeftRightBuffer::new_cloned(LinearMap::new()),
+        }
+    }
+
+    /// Inserts `value` for `key` into the pending map and returns the replaced value.
+    ///
+    /// # Errors
+    /// Returns the entry back if the map is full.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, otherwise it might panic as this could violate the assumptions.
+    pub fn insert(&self, key: K, value: V) -> Result<Option<V>, (K, V)> {
+        self.buffer.write().insert(key, value)
+    }
+
+    /// Removes `key` from the pending map and returns its value.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, otherwise it might panic as this could violate the assumptions.
+    pub fn remove(&self, key: &K) -> Option<V> {
+        self.buffer.write().remove(key)
+    }
+
+    /// Publishes the pending map with all updates since the last publication.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, otherwise it might panic as this could violate the assumptions.
+    pub fn publish(&self) {
+        let pending = self.buffer.write();
+        self.buffer.publish(pending);
+    }
+
+    /// Returns a copy of the published value of `key`.
+    #[must_use]
+    pub fn get(&self, key: &K) -> Option<V> {
+        self.buffer.read().get(key).cloned()
+    }
+
+    /// Returns a read guard of the published map, e.g. to iterate over all entries.
+    #[must_use]
+    pub fn read(&self) -> ReadGuard<'_, LinearMap<K, V, N>> {
+        self.buffer.read()
+    }
+}
+
+impl<K: Clone + Eq, V: Clone, const N: usize> Default for LeftRightMap<K, V, N> {
+    fn default() -> LeftRightMap<K, V, N> {
+        LeftRightMap::new()
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_readers_see_whole_publications() {
+        let limits = LeftRightMap::<char, i16, 2>::new();
+        assert_eq!(limits.insert('x', 100), Ok(None));
+        assert_eq!(limits.insert('y', 50), Ok(None));
+        assert_eq!(limits.insert('z', 10), Err(('z', 10)), "the map is full");
+        assert_eq!(limits.get(&'x'), None, "the updates are pending");
+        limits.publish();
+        assert_eq!(limits.read().len(), 2);
+
+        assert_eq!(limits.insert('x', 120), Ok(Some(100)));
+        assert_eq!(limits.remove(&'y'), Some(50));
+        assert_eq!(limits.get(&'y'), Some(50), "the updates are pending");
+        limits.publish();
+        assert_eq!(limits.get(&'x'), Some(120));
+        assert_eq!(limits.get(&'y'), None);
+
+        limits.publish();
+        assert_eq!(
+            limits.get(&'x'),
+            Some(120),
+            "the pending map survives the publication"
+        );
+    }
+}
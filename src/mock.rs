@@ -0,0 +1,347 @@
+use crate::guard::NotSend;
+use crate::{Error, Generation, Side, Violation, fault};
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use spin::Mutex;
+use std::collections::VecDeque;
+use std::vec::Vec;
+
+/// Test double of [`LeftRightBuffer`][crate::LeftRightBuffer] for host-side application tests.
+///
+/// It offers the same API, but its corner cases can be scripted deterministically:
+/// - [`preload()`][MockLeftRightBuffer::preload] queues values, which get published one by one to the next reads.
+/// - [`contend_on_read()`][MockLeftRightBuffer::contend_on_read] forces the special circumstance on the nth read.
+/// - [`fail_on_publish()`][MockLeftRightBuffer::fail_on_publish] loses the nth publication.
+///
+/// Reads and publications are counted from 1 on, starting with the construction of the mock.
+pub struct MockLeftRightBuffer<T> {
+    script: Mutex<Script<T>>,
+}
+
+/// The scripted behavior and the data of a [`MockLeftRightBuffer`].
+struct Script<T> {
+    published: T,
+    previous: T,
+    pending: T,
+    write_side: Side,
+    writer_active: bool,
+    sealed: bool,
+    generation: u32,
+    preloaded: VecDeque<T>,
+    reads: usize,
+    contended_reads: Vec<usize>,
+    publications: usize,
+    failed_publications: Vec<usize>,
+}
+
+/// Read guard of a [`MockLeftRightBuffer`] holding a copy of the read value.
+pub struct MockReadGuard<T> {
+    value: T,
+}
+
+/// Write guard of a [`MockLeftRightBuffer`] holding the pending value.
+pub struct MockWriteGuard<'a, T> {
+    buffer: &'a MockLeftRightBuffer<T>,
+    value: T,
+    _not_send: NotSend,
+}
+
+impl<T: Copy> MockLeftRightBuffer<T> {
+    /// Generates a new [`MockLeftRightBuffer`] and takes the data.
+    pub const fn new(data: T) -> MockLeftRightBuffer<T> {
+        MockLeftRightBuffer {
+            script: Mutex::new(Script {
+                published: data,
+                previous: data,
+                pending: data,
+                write_side: Side::Right,
+                writer_active: false,
+                sealed: false,
+                generation: 0,
+                preloaded: VecDeque::new(),
+                reads: 0,
+                contended_reads: Vec::new(),
+                publications: 0,
+                failed_publications: Vec::new(),
+            }),
+        }
+    }
+
+    /// Queues `values`, which get published one by one to the next reads, as if a writer published in between.
+    pub fn preload(&self, values: impl IntoIterator<Item = T>) {
+        self.script.lock().preloaded.extend(values);
+    }
+
+    /// Forces the special circumstance on the `nth` read.
+    ///
+    /// [`read()`][MockLeftRightBuffer::read] returns the previously published value,
+    /// [`try_read()`][MockLeftRightBuffer::try_read] returns `None` and
+    /// [`read_bounded()`][MockLeftRightBuffer::read_bounded] returns [`Error::Contention`].
+    pub fn contend_on_read(&self, nth: usize) {
+        self.script.lock().contended_reads.push(nth);
+    }
+
+    /// Loses the `nth` publication, so that the readers keep the previously published value.
+    pub fn fail_on_publish(&self, nth: usize) {
+        self.script.lock().failed_publications.push(nth);
+    }
+
+    /// Returns a read guard, see [`LeftRightBuffer::read()`][crate::LeftRightBuffer::read].
+    pub fn read(&self) -> MockReadGuard<T> {
+        let value = match self.next_read() {
+            Ok(value) | Err(value) => value,
+        };
+        MockReadGuard { value }
+    }
+
+    /// Returns a read guard, if it is available without waiting, see [`LeftRightBuffer::try_read()`][crate::LeftRightBuffer::try_read].
+    pub fn try_read(&self) -> Option<MockReadGuard<T>> {
+        self.next_read().ok().map(|value| MockReadGuard { value })
+    }
+
+    /// Returns a read guard, see [`LeftRightBuffer::read_bounded()`][crate::LeftRightBuffer::read_bounded].
+    ///
+    /// # Errors
+    /// [`Error::Contention`] on a read forced by [`contend_on_read()`][MockLeftRightBuffer::contend_on_read].
+    pub fn read_bounded(&self, _spins: u32) -> Result<MockReadGuard<T>, Error> {
+        self.next_read()
+            .map(|value| MockReadGuard { value })
+            .map_err(|_| Error::Contention)
+    }
+
+    /// Returns a read guard of the newest published value, see [`LeftRightBuffer::read_latest()`][crate::LeftRightBuffer::read_latest].
+    pub fn read_latest(&self) -> MockReadGuard<T> {
+        let mut script = self.script.lock();
+        let value = match script.next_read() {
+            Ok(value) => value,
+            Err(_) => script.published, // it never falls back to the previous value
+        };
+        MockReadGuard { value }
+    }
+
+    /// Returns the number of publications so far, see [`LeftRightBuffer::generation()`][crate::LeftRightBuffer::generation].
+    pub fn generation(&self) -> Generation {
+        Generation::new(self.script.lock().generation)
+    }
+
+    /// Makes the mock permanently read-only, see [`LeftRightBuffer::seal()`][crate::LeftRightBuffer::seal].
+    pub fn seal(&self) {
+        self.script.lock().sealed = true;
+    }
+
+    /// Returns `true` if the mock got sealed, see [`LeftRightBuffer::is_sealed()`][crate::LeftRightBuffer::is_sealed].
+    pub fn is_sealed(&self) -> bool {
+        self.script.lock().sealed
+    }
+
+    /// Returns a write guard starting with the published value, see [`LeftRightBuffer::write()`][crate::LeftRightBuffer::write].
+    ///
+    /// # Panics
+    /// There is already a writer or the mock got sealed.
+    pub fn write(&self) -> MockWriteGuard<'_, T> {
+        match self.try_writer(true) {
+            Ok(thing) => thing,
+            Err(violation) => fault::violated(violation, None, "write()"),
+        }
+    }
+
+    /// Returns a write guard like [`write()`][MockLeftRightBuffer::write], but returns an error instead of handling the contract violation.
+    ///
+    /// # Errors
+    /// [`Error::Violation`] as there is already a writer or the mock got sealed.
+    pub fn try_write(&self) -> Result<MockWriteGuard<'_, T>, Error> {
+        self.try_writer(true).map_err(Error::Violation)
+    }
+
+    /// Returns a write guard like [`write()`][MockLeftRightBuffer::write], but gives up after `spins` retries.
+    ///
+    /// # Errors
+    /// [`Error::Contention`] as there is already a writer or the mock got sealed.
+    pub fn write_bounded(&self, _spins: u32) -> Result<MockWriteGuard<'_, T>, Error> {
+        self.try_writer(true).map_err(|_| Error::Contention)
+    }
+
+    /// Returns a write guard starting with the pending value, see [`LeftRightBuffer::write_without_sync()`][crate::LeftRightBuffer::write_without_sync].
+    ///
+    /// # Panics
+    /// There is already a writer or the mock got sealed.
+    pub fn write_without_sync(&self) -> MockWriteGuard<'_, T> {
+        match self.try_writer(false) {
+            Ok(thing) => thing,
+            Err(violation) => fault::violated(violation, None, "write_without_sync()"),
+        }
+    }
+
+    /// Returns a write guard like [`write_without_sync()`][MockLeftRightBuffer::write_without_sync], but returns an error instead of handling the contract violation.
+    ///
+    /// # Errors
+    /// [`Error::Violation`] as there is already a writer or the mock got sealed.
+    pub fn try_write_without_sync(&self) -> Result<MockWriteGuard<'_, T>, Error> {
+        self.try_writer(false).map_err(Error::Violation)
+    }
+
+    /// Makes the value of the write guard visible to the readers, unless the publication is forced to fail.
+    pub fn publish(&self, writer: MockWriteGuard<'_, T>) {
+        let value = writer.value;
+        drop(writer);
+        let mut script = self.script.lock();
+        script.pending = value;
+        script.publications += 1;
+        if script.failed_publications.contains(&script.publications) {
+            return;
+        }
+        script.previous = script.published;
+        script.published = value;
+        script.generation = script.generation.wrapping_add(1);
+        script.write_side = match script.write_side {
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        };
+    }
+
+    /// Returns a copy of the published value, see [`LeftRightBuffer::load()`][crate::LeftRightBuffer::load].
+    pub fn load(&self) -> T {
+        *self.read()
+    }
+
+    /// Replaces the pending value with `data` and publishes it, see [`LeftRightBuffer::store()`][crate::LeftRightBuffer::store].
+    ///
+    /// # Panics
+    /// There is already a writer or the mock got sealed.
+    pub fn store(&self, data: T) {
+        let mut writer = self.write_without_sync();
+        *writer = data;
+        self.publish(writer);
+    }
+
+    /// Returns the value of the next read, or the previous value on a contended read.
+    fn next_read(&self) -> Result<T, T> {
+        self.script.lock().next_read()
+    }
+
+    /// Returns the write guard, which starts with the published value if `synced`.
+    fn try_writer(&self, synced: bool) -> Result<MockWriteGuard<'_, T>, Violation> {
+        let mut script = self.script.lock();
+        if script.sealed {
+            return Err(Violation::Sealed);
+        }
+        if script.writer_active {
+            return Err(Violation::Write(script.write_side));
+        }
+        script.writer_active = true;
+        let value = match synced {
+            true => script.published,
+            false => script.pending,
+        };
+        Ok(MockWriteGuard {
+            buffer: self,
+            value,
+            _not_send: PhantomData,
+        })
+    }
+}
+
+impl<T: Copy> Script<T> {
+    /// Counts the read and publishes the next preloaded value.
+    fn next_read(&mut self) -> Result<T, T> {
+        self.reads += 1;
+        if let Some(value) = self.preloaded.pop_front() {
+            self.previous = self.published;
+            self.published = value;
+            self.generation = self.generation.wrapping_add(1);
+        }
+        match self.contended_reads.contains(&self.reads) {
+            true => Err(self.previous),
+            false => Ok(self.published),
+        }
+    }
+}
+
+impl<T> Deref for MockReadGuard<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> Deref for MockWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for MockWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T> Drop for MockWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        self.buffer.script.lock().writer_active = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preloaded_values_get_read_one_by_one() {
+        let buffer = MockLeftRightBuffer::new(0u8);
+        buffer.preload([1, 2]);
+        assert_eq!(*buffer.read(), 1);
+        assert_eq!(*buffer.read(), 2);
+        assert_eq!(*buffer.read(), 2);
+        assert_eq!(buffer.generation(), 2);
+    }
+
+    #[test]
+    fn the_nth_read_gets_contended() {
+        let buffer = MockLeftRightBuffer::new(0u8);
+        buffer.store(1);
+        buffer.contend_on_read(2);
+        buffer.contend_on_read(3);
+        assert_eq!(*buffer.read(), 1);
+        assert!(buffer.try_read().is_none(), "the second read is contended");
+        assert_eq!(buffer.read_bounded(3).err(), Some(Error::Contention));
+        assert_eq!(*buffer.read(), 1);
+    }
+
+    #[test]
+    fn contended_reads_return_the_previous_value() {
+        let buffer = MockLeftRightBuffer::new(0u8);
+        buffer.store(1);
+        buffer.contend_on_read(1);
+        assert_eq!(*buffer.read(), 0);
+        assert_eq!(*buffer.read(), 1);
+    }
+
+    #[test]
+    fn the_nth_publication_gets_lost() {
+        let buffer = MockLeftRightBuffer::new(0u8);
+        buffer.fail_on_publish(1);
+        buffer.store(1);
+        assert_eq!(buffer.load(), 0);
+        assert_eq!(buffer.generation(), 0);
+
+        let writer = buffer.write_without_sync();
+        assert_eq!(*writer, 1);
+        buffer.publish(writer);
+        assert_eq!(buffer.load(), 1);
+    }
+
+    #[test]
+    fn a_second_writer_gets_reported() {
+        let buffer = MockLeftRightBuffer::new(0u8);
+        let _writer = buffer.write();
+        assert_eq!(
+            buffer.try_write().err(),
+            Some(Error::Violation(Violation::Write(Side::Right)))
+        );
+        assert_eq!(buffer.write_bounded(3).err(), Some(Error::Contention));
+    }
+}
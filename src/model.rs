@@ -0,0 +1,131 @@
+//! Model test of the swap logic.
+//!
+//! Random sequences of operations get applied to a [`LeftRightBuffer`] and to a reference model,
+//! which is a single variable where the last publication wins.
+
+use crate::LeftRightBuffer;
+use proptest::prelude::*;
+
+type Payload = (u32, u32);
+
+#[derive(Debug, Clone)]
+enum Operation {
+    /// Reads the published value.
+    Read,
+    /// Reads the published value without waiting.
+    TryRead,
+    /// Replaces the pending value and publishes it.
+    Store(Payload),
+    /// Modifies the pending value partially, while readers interrupt the writer.
+    Update {
+        first: Option<u32>,
+        second: Option<u32>,
+        publish: bool,
+    },
+}
+
+/// The reference model.
+#[derive(Default)]
+struct Model {
+    published: Payload,
+    // the unpublished value of an abandoned writer, which the next writer continues with
+    abandoned: Option<Payload>,
+    generation: u32,
+}
+
+fn operation() -> impl Strategy<Value = Operation> {
+    prop_oneof![
+        Just(Operation::Read),
+        Just(Operation::TryRead),
+        any::<Payload>().prop_map(Operation::Store),
+        (any::<Option<u32>>(), any::<Option<u32>>(), any::<bool>()).prop_map(
+            |(first, second, publish)| Operation::Update {
+                first,
+                second,
+                publish,
+            }
+        ),
+    ]
+}
+
+fn apply(buffer: &LeftRightBuffer<Payload>, model: &mut Model, operation: &Operation) {
+    match *operation {
+        Operation::Read => assert_eq!(
+            *buffer.read(),
+            model.published,
+            "the reader missed the publication"
+        ),
+        Operation::TryRead => assert_eq!(
+            buffer.try_read().map(|thing| *thing),
+            Some(model.published),
+            "the reader missed the publication"
+        ),
+        Operation::Store(value) => {
+            buffer.store(value);
+            model.published = value;
+            model.abandoned = None;
+            model.generation += 1;
+        }
+        Operation::Update {
+            first,
+            second,
+            publish,
+        } => {
+            let mut writer = buffer.write();
+            let mut pending = model.abandoned.unwrap_or(model.published);
+            assert_eq!(
+                *writer, pending,
+                "the writer did not start with the synced value"
+            );
+            if let Some(first) = first {
+                writer.0 = first;
+                pending.0 = first;
+            }
+            assert_eq!(
+                *buffer.read(),
+                model.published,
+                "the reader saw the pending value"
+            );
+            if let Some(second) = second {
+                writer.1 = second;
+                pending.1 = second;
+            }
+            assert_eq!(
+                *buffer.read(),
+                model.published,
+                "the reader saw the pending value"
+            );
+            match publish {
+                true => {
+                    buffer.publish(writer);
+                    model.published = pending;
+                    model.abandoned = None;
+                    model.generation += 1;
+                }
+                false => {
+                    drop(writer);
+                    model.abandoned = Some(pending);
+                }
+            }
+        }
+    }
+    assert_eq!(
+        buffer.generation(),
+        model.generation,
+        "a publication was not counted"
+    );
+}
+
+proptest! {
+    #[test]
+    fn the_buffer_behaves_like_a_single_variable(
+        initial in any::<Payload>(),
+        operations in proptest::collection::vec(operation(), 0..64)
+    ) {
+        let buffer = LeftRightBuffer::new(initial);
+        let mut model = Model { published: initial, ..Model::default() };
+        for operation in &operations {
+            apply(&buffer, &mut model, operation);
+        }
+    }
+}
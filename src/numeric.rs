@@ -0,0 +1,158 @@
+use crate::LeftRightBuffer;
+use core::num::{Saturating, Wrapping};
+
+/// Payloads of [`add_and_publish()`][LeftRightBuffer::add_and_publish], whose addition states how it handles an overflow.
+///
+/// The addition never panics: the integers saturate at their bounds and the floats reach the infinities.
+/// [`Wrapping`] wraps around and [`Saturating`] saturates.
+pub trait Accumulate: Copy {
+    /// Returns the sum of `self` and `delta`.
+    #[must_use]
+    fn accumulate(self, delta: Self) -> Self;
+}
+
+macro_rules! impl_accumulate_int {
+    ($($t:ty),*) => {
+        $(
+            impl Accumulate for $t {
+                fn accumulate(self, delta: $t) -> $t {
+                    self.saturating_add(delta)
+                }
+            }
+
+            impl Accumulate for Wrapping<$t> {
+                fn accumulate(self, delta: Wrapping<$t>) -> Wrapping<$t> {
+                    self + delta
+                }
+            }
+
+            impl Accumulate for Saturating<$t> {
+                fn accumulate(self, delta: Saturating<$t>) -> Saturating<$t> {
+                    self + delta
+                }
+            }
+        )*
+    };
+}
+
+impl_accumulate_int!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+macro_rules! impl_accumulate_float {
+    ($($t:ty),*) => {
+        $(
+            impl Accumulate for $t {
+                fn accumulate(self, delta: $t) -> $t {
+                    self + delta
+                }
+            }
+        )*
+    };
+}
+
+impl_accumulate_float!(f32, f64);
+
+impl<T: Accumulate> LeftRightBuffer<T> {
+    /// Adds `delta` to the last written value and publishes the result.
+    ///
+    /// An overflow gets handled as stated by [`Accumulate`], e.g. a `u32` saturates at `u32::MAX`.
+    /// Returns the published value.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, see [`write()`][LeftRightBuffer::write].
+    pub fn add_and_publish(&self, delta: T) -> T {
+        let mut writer = self.write();
+        *writer = writer.accumulate(delta);
+        let value = *writer;
+        self.publish(writer);
+        value
+    }
+}
+
+impl<T: Copy + PartialOrd> LeftRightBuffer<T> {
+    /// Publishes the maximum of the last written value and `value`.
+    ///
+    /// Returns the published value.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, see [`write()`][LeftRightBuffer::write].
+    pub fn store_max(&self, value: T) -> T {
+        let mut writer = self.write();
+        if value > *writer {
+            *writer = value;
+        }
+        let value = *writer;
+        self.publish(writer);
+        value
+    }
+
+    /// Publishes the minimum of the last written value and `value`.
+    ///
+    /// Returns the published value.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, see [`write()`][LeftRightBuffer::write].
+    pub fn store_min(&self, value: T) -> T {
+        let mut writer = self.write();
+        if value < *writer {
+            *writer = value;
+        }
+        let value = *writer;
+        self.publish(writer);
+        value
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use crate::LeftRightBuffer;
+    use core::num::Wrapping;
+
+    #[test]
+    fn add_and_publish_accumulates() {
+        let buffer = LeftRightBuffer::new(10u32);
+        assert_eq!(buffer.add_and_publish(5), 15);
+        assert_eq!(buffer.add_and_publish(5), 20);
+        assert_eq!(*buffer.read(), 20);
+    }
+
+    #[test]
+    fn add_and_publish_never_overflows() {
+        let saturating = LeftRightBuffer::new(250u8);
+        assert_eq!(saturating.add_and_publish(10), u8::MAX);
+        let wrapping = LeftRightBuffer::new(Wrapping(250u8));
+        assert_eq!(wrapping.add_and_publish(Wrapping(10)), Wrapping(4));
+        let float = LeftRightBuffer::new(f32::MAX);
+        assert_eq!(
+            float.add_and_publish(f32::MAX).to_bits(),
+            f32::INFINITY.to_bits()
+        );
+    }
+
+    #[test]
+    fn store_max_only_needs_an_ordering() {
+        #[derive(Clone, Copy, PartialEq, PartialOrd)]
+        struct Version(u8, u8);
+
+        let buffer = LeftRightBuffer::new(Version(1, 2));
+        buffer.store_max(Version(1, 0));
+        assert!(*buffer.read() == Version(1, 2));
+        buffer.store_max(Version(2, 0));
+        assert!(*buffer.read() == Version(2, 0));
+    }
+
+    #[test]
+    fn store_max_and_min_keep_the_extremum() {
+        let max = LeftRightBuffer::new(1.5f32);
+        assert_eq!(max.store_max(0.5).to_bits(), 1.5f32.to_bits());
+        assert_eq!(max.store_max(2.5).to_bits(), 2.5f32.to_bits());
+        assert_eq!(max.read().to_bits(), 2.5f32.to_bits());
+
+        let min = LeftRightBuffer::new(0i16);
+        assert_eq!(min.store_min(3), 0);
+        assert_eq!(min.store_min(-3), -3);
+        assert_eq!(*min.read(), -3);
+    }
+}
@@ -0,0 +1,52 @@
+use crate::LeftRightBuffer;
+
+impl<T: Clone> LeftRightBuffer<Option<T>> {
+    /// Publishes `Some(value)`.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, see [`store()`][LeftRightBuffer::store].
+    pub fn publish_some(&self, value: T) {
+        self.store(Some(value));
+    }
+
+    /// Publishes `None`.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, see [`store()`][LeftRightBuffer::store].
+    pub fn publish_none(&self) {
+        self.store(None);
+    }
+
+    /// Takes the last written value out of the buffer and publishes `None`.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, see [`write()`][LeftRightBuffer::write].
+    pub fn take_published(&self) -> Option<T> {
+        let mut writer = self.write();
+        let value = writer.take();
+        self.publish(writer);
+        value
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use crate::LeftRightBuffer;
+
+    #[test]
+    fn optional_values_get_published_and_taken() {
+        let fix = LeftRightBuffer::new(None);
+        fix.publish_some(3u32);
+        assert_eq!(*fix.read(), Some(3));
+        assert_eq!(fix.take_published(), Some(3));
+        assert_eq!(*fix.read(), None);
+        assert_eq!(fix.take_published(), None);
+
+        fix.publish_some(4);
+        fix.publish_none();
+        assert_eq!(*fix.read(), None);
+        // the sync must not resurrect the old value
+        assert_eq!(*fix.write(), None);
+    }
+}
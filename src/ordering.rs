@@ -0,0 +1,33 @@
+//! Memory orderings used for the atomics of this crate.
+//!
+//! By default, the orderings are chosen for the single core environment of the assumptions.
+//! With the `strict-ordering` feature, every access is sequentially consistent, which is suitable for multi-core or out-of-order environments.
+//! The same holds on WebAssembly with the atomics proposal, where the buffer may be shared between web workers.
+
+#[cfg(not(any(
+    feature = "strict-ordering",
+    all(target_family = "wasm", target_feature = "atomics")
+)))]
+mod chosen {
+    use core::sync::atomic::Ordering;
+
+    pub(crate) const RELAXED: Ordering = Ordering::Relaxed;
+    pub(crate) const ACQUIRE: Ordering = Ordering::Acquire;
+    pub(crate) const RELEASE: Ordering = Ordering::Release;
+    pub(crate) const ACQ_REL: Ordering = Ordering::AcqRel;
+}
+
+#[cfg(any(
+    feature = "strict-ordering",
+    all(target_family = "wasm", target_feature = "atomics")
+))]
+mod chosen {
+    use core::sync::atomic::Ordering;
+
+    pub(crate) const RELAXED: Ordering = Ordering::SeqCst;
+    pub(crate) const ACQUIRE: Ordering = Ordering::SeqCst;
+    pub(crate) const RELEASE: Ordering = Ordering::SeqCst;
+    pub(crate) const ACQ_REL: Ordering = Ordering::SeqCst;
+}
+
+pub(crate) use chosen::{ACQ_REL, ACQUIRE, RELAXED, RELEASE};
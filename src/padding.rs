@@ -0,0 +1,81 @@
+use core::ops::{Deref, DerefMut};
+
+/// Aligns and pads the wrapped value to the length of a cache line.
+///
+/// Placing data accessed by different cores into different cache lines avoids false sharing on SMP systems.
+/// With the `cache-padded` feature, [`LeftRightBuffer`][crate::LeftRightBuffer] uses it for both copies of the data and its control state.
+#[cfg_attr(
+    any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        target_arch = "powerpc64"
+    ),
+    repr(align(128))
+)]
+#[cfg_attr(
+    not(any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        target_arch = "powerpc64"
+    )),
+    repr(align(64))
+)]
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct CachePadded<T> {
+    value: T,
+}
+
+impl<T> CachePadded<T> {
+    /// Wraps `value`.
+    pub const fn new(value: T) -> CachePadded<T> {
+        CachePadded { value }
+    }
+
+    /// Returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> Deref for CachePadded<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for CachePadded<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+#[cfg(feature = "cache-padded")]
+pub(crate) type Slot<T> = CachePadded<T>;
+
+#[cfg(not(feature = "cache-padded"))]
+pub(crate) type Slot<T> = T;
+
+#[cfg(feature = "cache-padded")]
+pub(crate) const fn slot<T>(value: T) -> Slot<T> {
+    CachePadded::new(value)
+}
+
+#[cfg(not(feature = "cache-padded"))]
+pub(crate) const fn slot<T>(value: T) -> Slot<T> {
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn padded_values_occupy_separate_cache_lines() {
+        let pair = [CachePadded::new(0u8), CachePadded::new(1u8)];
+        let distance = core::ptr::from_ref(&pair[1]).addr() - core::ptr::from_ref(&pair[0]).addr();
+        assert!(distance >= 64, "the values share a cache line");
+        assert_eq!(*pair[1], 1);
+    }
+}
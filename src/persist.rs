@@ -0,0 +1,469 @@
+//! Flash persistence of the `persistence` feature, see [`Persistence`].
+
+use crate::{LeftRightBuffer, WriteGuard, clock};
+use bytemuck::Pod;
+use core::ops::Range;
+use embedded_storage::nor_flash::NorFlash;
+
+/// Size of the trailer behind the snapshot.
+const TRAILER: usize = 8;
+
+/// When [`Persistence`] writes the published value to the flash.
+#[cfg_attr(any(test, not(feature = "fmt-free")), derive(Debug))]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PersistPolicy {
+    /// After every publication.
+    EveryPublish,
+    /// Only with [`persist()`][Persistence::persist].
+    OnDemand,
+    /// After a publication, if the last write is at least the given number of ticks of the registered [`Clock`][crate::Clock] ago.
+    ///
+    /// It limits the wear of the flash. Without a clock, every publication gets persisted.
+    RateLimited(u32),
+}
+
+impl PersistPolicy {
+    /// Returns [`PersistPolicy::RateLimited`] with the interval as a duration in ticks of the registered [`Clock`][crate::Clock].
+    #[cfg(feature = "fugit")]
+    #[must_use]
+    pub const fn rate_limited<const NOM: u32, const DENOM: u32>(
+        interval: fugit::Duration<u32, NOM, DENOM>,
+    ) -> PersistPolicy {
+        PersistPolicy::RateLimited(interval.ticks())
+    }
+}
+
+/// Failures of [`Persistence`].
+#[cfg_attr(any(test, not(feature = "fmt-free")), derive(Debug))]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PersistError<E> {
+    /// The region is not aligned to the erase size of the flash, or the region or the scratch buffer is too small.
+    Layout,
+    /// The flash reported the error.
+    Flash(E),
+}
+
+/// Writer side of a [`LeftRightBuffer`], which persists the published value in a region of a NOR flash.
+///
+/// The value gets written as a snapshot of [`dump_snapshot()`][LeftRightBuffer::dump_snapshot], protected by a CRC and a sequence number.
+///
+/// The region of the flash holds two slots of [`slot_len()`][Persistence::slot_len] bytes each.
+/// Every write goes to the slot, which does not hold the newest record, so a power loss mid-write only tears the new record.
+/// A record consists of a snapshot and a trailer, padded with erased bytes:
+///
+/// | Offset | Size | Content |
+/// |---|---|---|
+/// | 0 | `snapshot_len()` | snapshot of [`dump_snapshot()`][LeftRightBuffer::dump_snapshot] |
+/// | `snapshot_len()` | 4 | sequence number, little endian |
+/// | `snapshot_len()` + 4 | 4 | CRC-32 (IEEE) of the snapshot and the sequence number, little endian |
+///
+/// At startup, the valid record with the newest sequence number wins.
+///
+/// The writer publishes with [`publish()`][Persistence::publish] instead of [`LeftRightBuffer::publish()`],
+/// which persists according to the [`PersistPolicy`].
+pub struct Persistence<'a, T, F> {
+    buffer: &'a LeftRightBuffer<T>,
+    flash: F,
+    start: u32,
+    slot_len: u32,
+    scratch: &'a mut [u8],
+    policy: PersistPolicy,
+    last_persisted: Option<u32>,
+    // The slot and the sequence number of the newest valid record.
+    newest: Option<(u32, u32)>,
+}
+
+impl<'a, T: Pod, F: NorFlash> Persistence<'a, T, F> {
+    /// Generates a new [`Persistence`] of `buffer` in the `region` of the `flash`.
+    ///
+    /// The `scratch` buffer holds a record while it gets written or read, see [`record_len()`][Persistence::record_len].
+    /// The region needs two slots, see [`slot_len()`][Persistence::slot_len]. Both get read to find the newest record.
+    ///
+    /// # Errors
+    /// - [`PersistError::Layout`] if the region does not start at an erase block or is too small for both slots,
+    ///   or if the scratch buffer is shorter than a record.
+    /// - [`PersistError::Flash`] if reading the slots failed.
+    pub fn new(
+        buffer: &'a LeftRightBuffer<T>,
+        flash: F,
+        region: Range<u32>,
+        scratch: &'a mut [u8],
+        policy: PersistPolicy,
+    ) -> Result<Persistence<'a, T, F>, PersistError<F::Error>> {
+        let slot_len = u32::try_from(Self::slot_len()).map_err(|_| PersistError::Layout)?;
+        let aligned =
+            usize::try_from(region.start).is_ok_and(|start| start.is_multiple_of(F::ERASE_SIZE));
+        let fits = slot_len
+            .checked_mul(2)
+            .and_then(|slots| region.start.checked_add(slots))
+            .is_some_and(|end| end <= region.end);
+        if !aligned || !fits || scratch.len() < Self::record_len() {
+            return Err(PersistError::Layout);
+        }
+        let mut persistence = Persistence {
+            buffer,
+            flash,
+            start: region.start,
+            slot_len,
+            scratch,
+            policy,
+            last_persisted: None,
+            newest: None,
+        };
+        for slot in 0..2 {
+            if let Some(sequence) = persistence.read_slot(slot)? {
+                let newer = persistence
+                    .newest
+                    .is_none_or(|(_, newest)| is_newer(sequence, newest));
+                if newer {
+                    persistence.newest = Some((slot, sequence));
+                }
+            }
+        }
+        Ok(persistence)
+    }
+
+    /// Returns the number of bytes of a record, which the scratch buffer has to hold.
+    ///
+    /// It is the [`snapshot_len()`][LeftRightBuffer::snapshot_len] and the trailer rounded up to the write and the read size of the flash.
+    #[must_use]
+    pub const fn record_len() -> usize {
+        (LeftRightBuffer::<T>::snapshot_len() + TRAILER)
+            .next_multiple_of(F::WRITE_SIZE)
+            .next_multiple_of(F::READ_SIZE)
+    }
+
+    /// Returns the number of bytes of a slot, which is the [`record_len()`][Persistence::record_len] rounded up to the erase size of the flash.
+    ///
+    /// The region has to hold two slots.
+    #[must_use]
+    pub const fn slot_len() -> usize {
+        Self::record_len().next_multiple_of(F::ERASE_SIZE)
+    }
+
+    /// Publishes the value of the write guard like [`LeftRightBuffer::publish()`] and persists it according to the policy.
+    ///
+    /// Returns `true` if the value got persisted. The publication happens even if the flash fails.
+    ///
+    /// # Errors
+    /// [`PersistError::Flash`] if erasing or writing the flash failed.
+    ///
+    /// # Panics
+    /// The guard belongs to another buffer or to a side, which got published meanwhile.
+    pub fn publish(&mut self, writer: WriteGuard<'_, T>) -> Result<bool, PersistError<F::Error>> {
+        self.buffer.publish(writer);
+        let due = match self.policy {
+            PersistPolicy::EveryPublish => true,
+            PersistPolicy::OnDemand => false,
+            PersistPolicy::RateLimited(interval) => match (self.last_persisted, clock::now()) {
+                (Some(last), Some(now)) => now.wrapping_sub(last) >= interval,
+                _ => true,
+            },
+        };
+        if due {
+            self.persist()?;
+        }
+        Ok(due)
+    }
+
+    /// Writes the published value to the flash, regardless of the policy.
+    ///
+    /// The record replaces the older slot, so the newest record stays intact until this one is complete.
+    /// Call it e.g. before a planned shutdown, if the policy skipped the last publications.
+    ///
+    /// # Errors
+    /// [`PersistError::Flash`] if erasing or writing the flash failed.
+    pub fn persist(&mut self) -> Result<(), PersistError<F::Error>> {
+        let (slot, sequence) = match self.newest {
+            Some((slot, sequence)) => (1 - slot, sequence.wrapping_add(1)),
+            None => (0, 0),
+        };
+        let record = &mut self.scratch[..Self::record_len()];
+        let written = self.buffer.dump_snapshot(record);
+        let (content, trailer) = record.split_at_mut(written);
+        trailer[..4].copy_from_slice(&sequence.to_le_bytes());
+        let crc = !crc32(crc32(u32::MAX, content), &trailer[..4]);
+        trailer[4..TRAILER].copy_from_slice(&crc.to_le_bytes());
+        // the padding looks like erased flash
+        trailer[TRAILER..].fill(0xFF);
+
+        let start = self.slot_start(slot);
+        self.flash
+            .erase(start, start + self.slot_len)
+            .map_err(PersistError::Flash)?;
+        self.flash
+            .write(start, &self.scratch[..Self::record_len()])
+            .map_err(PersistError::Flash)?;
+        self.newest = Some((slot, sequence));
+        self.last_persisted = clock::now();
+        Ok(())
+    }
+
+    /// Reads the newest valid record back from the flash, e.g. as the loader of [`init_from()`][LeftRightBuffer::init_from] at startup.
+    ///
+    /// Returns `None` if the flash fails or no slot holds a valid record, e.g. as nothing got persisted yet.
+    pub fn restore(&mut self) -> Option<T> {
+        let (slot, sequence) = self.newest?;
+        if self.read_slot(slot).ok()? != Some(sequence) {
+            return None;
+        }
+        LeftRightBuffer::snapshot_value(&self.scratch[..LeftRightBuffer::<T>::snapshot_len()])
+    }
+
+    /// Returns the flash, e.g. to persist another buffer in another region.
+    pub fn release(self) -> F {
+        self.flash
+    }
+
+    /// Returns the address of the slot 0 or 1.
+    fn slot_start(&self, slot: u32) -> u32 {
+        self.start + slot * self.slot_len
+    }
+
+    /// Reads the record of the slot into the scratch buffer and returns its sequence number, if it is valid.
+    fn read_slot(&mut self, slot: u32) -> Result<Option<u32>, PersistError<F::Error>> {
+        let start = self.slot_start(slot);
+        let record = &mut self.scratch[..Self::record_len()];
+        self.flash
+            .read(start, record)
+            .map_err(PersistError::Flash)?;
+        let (content, trailer) = record.split_at(LeftRightBuffer::<T>::snapshot_len());
+        let sequence = u32::from_le_bytes([trailer[0], trailer[1], trailer[2], trailer[3]]);
+        let crc = u32::from_le_bytes([trailer[4], trailer[5], trailer[6], trailer[7]]);
+        let valid = !crc32(crc32(u32::MAX, content), &trailer[..4]) == crc;
+        Ok(valid.then_some(sequence))
+    }
+}
+
+/// Returns `true` if the sequence number `a` follows `b`, also across the wrap around.
+fn is_newer(a: u32, b: u32) -> bool {
+    a != b && a.wrapping_sub(b) < (1 << 31)
+}
+
+/// Continues the CRC-32 (IEEE, reflected) `crc` over `bytes`, without the final inversion.
+///
+/// It works bitwise without a table, as the records are small and get written rarely.
+fn crc32(mut crc: u32, bytes: &[u8]) -> u32 {
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    crc
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+    use crate::clock::test_clock;
+    use embedded_storage::nor_flash::{ErrorType, NorFlashErrorKind, ReadNorFlash};
+
+    /// Flash simulated in RAM with erase blocks of 64 bytes.
+    struct RamFlash {
+        bytes: [u8; 256],
+        writes: u32,
+        // The number of bytes, which still get written before a simulated power loss.
+        power_left: Option<usize>,
+    }
+
+    impl RamFlash {
+        fn new() -> RamFlash {
+            RamFlash {
+                bytes: [0xFF; 256],
+                writes: 0,
+                power_left: None,
+            }
+        }
+
+        fn range(&self, offset: u32, len: usize) -> Result<Range<usize>, NorFlashErrorKind> {
+            let start = usize::try_from(offset).map_err(|_| NorFlashErrorKind::OutOfBounds)?;
+            let end = start + len;
+            if end > self.bytes.len() {
+                return Err(NorFlashErrorKind::OutOfBounds);
+            }
+            Ok(start..end)
+        }
+    }
+
+    impl ErrorType for RamFlash {
+        type Error = NorFlashErrorKind;
+    }
+
+    impl ReadNorFlash for RamFlash {
+        const READ_SIZE: usize = 1;
+
+        fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), NorFlashErrorKind> {
+            let range = self.range(offset, bytes.len())?;
+            bytes.copy_from_slice(&self.bytes[range]);
+            Ok(())
+        }
+
+        fn capacity(&self) -> usize {
+            self.bytes.len()
+        }
+    }
+
+    impl NorFlash for RamFlash {
+        const WRITE_SIZE: usize = 4;
+        const ERASE_SIZE: usize = 64;
+
+        fn erase(&mut self, from: u32, to: u32) -> Result<(), NorFlashErrorKind> {
+            let len = usize::try_from(to - from).map_err(|_| NorFlashErrorKind::OutOfBounds)?;
+            let range = self.range(from, len)?;
+            self.bytes[range].fill(0xFF);
+            Ok(())
+        }
+
+        fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), NorFlashErrorKind> {
+            let range = self.range(offset, bytes.len())?;
+            for (cell, byte) in self.bytes[range].iter_mut().zip(bytes) {
+                if let Some(power_left) = &mut self.power_left {
+                    let Some(left) = power_left.checked_sub(1) else {
+                        return Err(NorFlashErrorKind::Other);
+                    };
+                    *power_left = left;
+                }
+                // programming only clears bits
+                *cell &= byte;
+            }
+            self.writes += 1;
+            Ok(())
+        }
+    }
+
+    fn persistence<'a, T: Pod>(
+        buffer: &'a LeftRightBuffer<T>,
+        flash: RamFlash,
+        scratch: &'a mut [u8],
+    ) -> Persistence<'a, T, RamFlash> {
+        Persistence::new(buffer, flash, 0..128, scratch, PersistPolicy::EveryPublish)
+            .expect("the layout fits")
+    }
+
+    #[test]
+    fn the_crc_matches_the_ieee_check_value() {
+        assert_eq!(!crc32(u32::MAX, b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn the_sequence_numbers_wrap_around() {
+        assert!(is_newer(0, u32::MAX), "0 follows the maximum");
+        assert!(!is_newer(u32::MAX, 0));
+        assert!(!is_newer(5, 5));
+    }
+
+    #[test]
+    fn the_layout_gets_checked() {
+        let buffer = LeftRightBuffer::new(0u32);
+        let mut scratch = [0; 20];
+        let misaligned = Persistence::new(
+            &buffer,
+            RamFlash::new(),
+            4..192,
+            &mut scratch,
+            PersistPolicy::OnDemand,
+        );
+        assert_eq!(misaligned.err(), Some(PersistError::Layout));
+        let single_slot = Persistence::new(
+            &buffer,
+            RamFlash::new(),
+            0..64,
+            &mut scratch,
+            PersistPolicy::OnDemand,
+        );
+        assert_eq!(single_slot.err(), Some(PersistError::Layout));
+        let mut short = [0; 16];
+        let too_short = Persistence::new(
+            &buffer,
+            RamFlash::new(),
+            0..128,
+            &mut short,
+            PersistPolicy::OnDemand,
+        );
+        assert_eq!(too_short.err(), Some(PersistError::Layout));
+    }
+
+    #[test]
+    fn the_policy_decides_about_the_writes() {
+        test_clock::register();
+        let buffer = LeftRightBuffer::new(0u16);
+        let mut scratch = [0; 20];
+        let mut persistence = Persistence::new(
+            &buffer,
+            RamFlash::new(),
+            64..192,
+            &mut scratch,
+            PersistPolicy::RateLimited(10),
+        )
+        .expect("the layout fits");
+
+        for value in 1..=3 {
+            let mut writer = buffer.write();
+            *writer = value;
+            let persisted = persistence.publish(writer).expect("the flash works");
+            assert_eq!(persisted, value == 1, "only the first value is due");
+        }
+        test_clock::advance(10);
+        assert_eq!(
+            persistence.publish(buffer.write()),
+            Ok(true),
+            "the interval passed"
+        );
+        assert_eq!(persistence.restore(), Some(3));
+        assert_eq!(persistence.release().writes, 2);
+    }
+
+    #[test]
+    fn the_persisted_value_survives_a_restart() {
+        let mut scratch = [0; 20];
+        let buffer = LeftRightBuffer::new(7u32);
+        let mut first = persistence(&buffer, RamFlash::new(), &mut scratch);
+        assert!(
+            !buffer.init_from(|| first.restore()),
+            "nothing got persisted yet"
+        );
+        assert_eq!(buffer.load(), 7, "the default got published");
+        let mut writer = buffer.write();
+        *writer = 8;
+        assert_eq!(first.publish(writer), Ok(true));
+        let flash = first.release();
+
+        let rebooted = LeftRightBuffer::new(7u32);
+        let mut second = persistence(&rebooted, flash, &mut scratch);
+        assert!(rebooted.init_from(|| second.restore()));
+        assert_eq!(rebooted.load(), 8);
+    }
+
+    #[test]
+    fn a_torn_write_keeps_the_previous_record() {
+        let mut scratch = [0; 20];
+        let buffer = LeftRightBuffer::new(0u32);
+        let mut first = persistence(&buffer, RamFlash::new(), &mut scratch);
+        for value in [1, 2] {
+            buffer.store(value);
+            assert_eq!(first.persist(), Ok(()));
+        }
+        buffer.store(3);
+        let mut flash = first.release();
+        flash.power_left = Some(10);
+        let mut torn = persistence(&buffer, flash, &mut scratch);
+        assert_eq!(
+            torn.persist(),
+            Err(PersistError::Flash(NorFlashErrorKind::Other)),
+            "the power got lost mid-write"
+        );
+        let mut flash = torn.release();
+        flash.power_left = None;
+
+        let rebooted = LeftRightBuffer::new(0u32);
+        let mut second = persistence(&rebooted, flash, &mut scratch);
+        assert!(rebooted.init_from(|| second.restore()));
+        assert_eq!(rebooted.load(), 2, "the torn record gets ignored");
+        rebooted.store(4);
+        assert_eq!(second.persist(), Ok(()));
+        assert_eq!(second.restore(), Some(4), "the torn slot got replaced");
+    }
+}
@@ -0,0 +1,193 @@
+//! Pipelined writes, see [`PipelinedBuffer`].
+
+use crate::sync::{AtomicU8, RwLock, RwLockWriteGuard};
+use crate::{LeftRightBuffer, ReadGuard, Scrubbed, ordering, scrubbed};
+use core::ops::{Deref, DerefMut};
+
+/// Marker of the newest slot, if there is no submitted value.
+const NONE: u8 = 0;
+
+/// Buffer, whose writer prepares the next values in `SLOTS` scratch slots, while a publication still settles.
+///
+/// On multi-core systems, readers on other cores may still hold the retired side after a publication,
+/// so the writer of a [`LeftRightBuffer`] has to wait before it writes again.
+/// Here, the writer prepares the next value in a free scratch slot instead and [`submit()`][PipelinedBuffer::submit]s it.
+/// The newest submitted value gets copied into the pending side and published, as soon as its readers are gone.
+/// Older submitted values, which never got published, are superseded. No call blocks.
+///
+/// With 2 slots, one value can wait for the publication while the next one gets prepared.
+/// With the `zeroize` feature, the scratch slots get scrubbed on drop like both copies of the data.
+///
+/// ```
+/// use minimal_left_right::PipelinedBuffer;
+///
+/// let samples = PipelinedBuffer::<[u16; 4], 2>::new([0; 4]);
+/// let mut next = samples.prepare().expect("a slot is free");
+/// next.copy_from_slice(&[1, 2, 3, 4]);
+/// assert!(samples.submit(next), "no reader holds the pending side");
+/// assert_eq!(*samples.read(), [1, 2, 3, 4]);
+/// ```
+pub struct PipelinedBuffer<T, const SLOTS: usize> {
+    buffer: LeftRightBuffer<T>,
+    scratch: [Scrubbed<RwLock<T>>; SLOTS],
+    // index + 1 of the slot with the newest submitted value, which is not published yet, or NONE
+    newest: AtomicU8,
+}
+
+/// Write guard of a scratch slot of a [`PipelinedBuffer`], see [`PipelinedBuffer::prepare()`].
+pub struct ScratchGuard<'a, T> {
+    guard: RwLockWriteGuard<'a, T>,
+    slot: u8,
+}
+
+impl<T> Deref for ScratchGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<T> DerefMut for ScratchGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.guard
+    }
+}
+
+impl<T: Clone, const SLOTS: usize> PipelinedBuffer<T, SLOTS> {
+    /// Generates a new [`PipelinedBuffer`], whose scratch slots start with clones of `data`.
+    ///
+    /// It does not compile with more than 254 slots.
+    pub fn new(data: T) -> PipelinedBuffer<T, SLOTS> {
+        const {
+            assert!(
+                SLOTS < u8::MAX as usize,
+                "a PipelinedBuffer has at most 254 slots"
+            );
+        };
+        PipelinedBuffer {
+            scratch: core::array::from_fn(|_| scrubbed(RwLock::new(data.clone()))),
+            buffer: LeftRightBuffer::new_cloned(data),
+            newest: AtomicU8::new(NONE),
+        }
+    }
+
+    /// Returns a write guard of a free scratch slot, which still holds an older value.
+    ///
+    /// Returns `None` if all slots are taken, e.g. the only slot holds the submitted value, which waits for its publication.
+    pub fn prepare(&self) -> Option<ScratchGuard<'_, T>> {
+        let newest = self.newest.load(ordering::ACQUIRE);
+        self.scratch
+            .iter()
+            .zip(1..=u8::MAX)
+            .filter(|&(_, marker)| marker != newest)
+            .find_map(|(slot, marker)| {
+                Some(ScratchGuard {
+                    guard: slot.try_write()?,
+                    slot: marker,
+                })
+            })
+    }
+
+    /// Submits the prepared value, which supersedes an older submitted value, and tries to publish it, see [`flush()`][PipelinedBuffer::flush].
+    ///
+    /// Returns `true` if the value got published right away.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, otherwise it might panic as this could violate the assumptions.
+    pub fn submit(&self, prepared: ScratchGuard<'_, T>) -> bool {
+        let slot = prepared.slot;
+        drop(prepared);
+        self.newest.store(slot, ordering::RELEASE);
+        self.flush()
+    }
+
+    /// Publishes the newest submitted value, if no reader holds the pending side anymore.
+    ///
+    /// Returns `false` if the submitted value still waits, so the writer can retry later, e.g. with its next submission.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, otherwise it might panic as this could violate the assumptions.
+    pub fn flush(&self) -> bool {
+        let newest = self.newest.load(ordering::ACQUIRE);
+        let Some(slot) = usize::from(newest)
+            .checked_sub(1)
+            .and_then(|index| self.scratch.get(index))
+        else {
+            return true;
+        };
+        let Ok(mut writer) = self.buffer.try_write_without_sync() else {
+            return false;
+        };
+        let Some(submitted) = slot.try_read() else {
+            return false;
+        };
+        writer.clone_from(&submitted);
+        drop(submitted);
+        self.newest.store(NONE, ordering::RELEASE);
+        self.buffer.publish(writer);
+        true
+    }
+
+    /// Returns `true` if a submitted value waits for its publication.
+    #[must_use]
+    pub fn is_pending(&self) -> bool {
+        self.newest.load(ordering::ACQUIRE) != NONE
+    }
+
+    /// Returns a read guard of the published value, see [`LeftRightBuffer::read()`].
+    #[must_use]
+    pub fn read(&self) -> ReadGuard<'_, T> {
+        self.buffer.read()
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_writer_prepares_while_the_publication_settles() {
+        let pipeline = PipelinedBuffer::<u32, 2>::new(0);
+        let held = pipeline.read();
+        let mut next = pipeline.prepare().expect("a slot is free");
+        *next = 1;
+        assert!(pipeline.submit(next), "the pending side is free");
+        assert_eq!(*held, 0, "the reader holds the retired side");
+
+        let mut next = pipeline.prepare().expect("a slot is free");
+        *next = 2;
+        assert!(!pipeline.submit(next), "the retired side is still held");
+        assert!(pipeline.is_pending());
+
+        let mut next = pipeline.prepare().expect("the other slot is free");
+        *next = 3;
+        assert!(!pipeline.submit(next), "the retired side is still held");
+        drop(held);
+        assert_eq!(*pipeline.read(), 1);
+        assert!(pipeline.flush());
+        assert!(!pipeline.is_pending());
+        assert_eq!(
+            *pipeline.read(),
+            3,
+            "the newest value supersedes the older one"
+        );
+        assert!(pipeline.flush(), "nothing waits");
+    }
+
+    #[test]
+    fn a_single_slot_waits_for_its_publication() {
+        let pipeline = PipelinedBuffer::<u8, 1>::new(0);
+        let held = pipeline.read();
+        let mut next = pipeline.prepare().expect("a slot is free");
+        *next = 1;
+        assert!(pipeline.submit(next));
+        let next = pipeline.prepare().expect("the slot is free");
+        assert!(!pipeline.submit(next));
+        assert!(pipeline.prepare().is_none(), "the slot waits");
+        drop(held);
+        assert!(pipeline.flush());
+        assert!(pipeline.prepare().is_some());
+    }
+}
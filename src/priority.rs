@@ -0,0 +1,109 @@
+//! Checks the assumption that the writer never interrupts a reader.
+//!
+//! With the `priority-check` feature, every read records the priority of the reader and every write and publish
+//! verifies that the priority of the writer is not higher than the one of the last reader.
+
+#[cfg(feature = "priority-check")]
+use crate::ordering;
+use crate::{Counter, LeftRightBuffer, Violation};
+
+/// Returns the priority of the calling task or interrupt. Higher values mean higher priorities.
+pub type PriorityCallback = fn() -> u8;
+
+#[cfg(feature = "priority-check")]
+static PRIORITY_CALLBACK: spin::Once<PriorityCallback> = spin::Once::new();
+
+/// Registers the callback, which returns the priority of the caller.
+///
+/// The callback can only be registered once. Returns `false` if there was already a callback.
+/// Without a callback, nothing gets checked.
+#[cfg(feature = "priority-check")]
+pub fn set_priority_callback(callback: PriorityCallback) -> bool {
+    let mut registered = false;
+    PRIORITY_CALLBACK.call_once(|| {
+        registered = true;
+        callback
+    });
+    registered
+}
+
+impl<T, G: Counter> LeftRightBuffer<T, G> {
+    /// Records the priority of the reader.
+    #[cfg_attr(
+        not(feature = "priority-check"),
+        expect(
+            clippy::unused_self,
+            reason = "nothing gets recorded without the priority-check feature"
+        )
+    )]
+    pub(crate) fn record_reader_priority(&self) {
+        #[cfg(feature = "priority-check")]
+        if let Some(callback) = PRIORITY_CALLBACK.get() {
+            self.last_reader_priority
+                .store(callback(), ordering::RELAXED);
+        }
+    }
+
+    /// Verifies that the priority of the writer is not higher than the one of the last reader.
+    #[cfg_attr(
+        not(feature = "priority-check"),
+        expect(
+            clippy::unused_self,
+            clippy::unnecessary_wraps,
+            reason = "nothing gets checked without the priority-check feature"
+        )
+    )]
+    pub(crate) fn check_writer_priority(&self) -> Result<(), Violation> {
+        #[cfg(feature = "priority-check")]
+        if let Some(callback) = PRIORITY_CALLBACK.get() {
+            let writer = callback();
+            let reader = self.last_reader_priority.load(ordering::RELAXED);
+            if writer > reader {
+                return Err(Violation::Priority { writer, reader });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+#[cfg(feature = "priority-check")]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+
+    std::thread_local! {
+        // per thread, so that the other tests running in parallel keep the priority 0
+        static PRIORITY: Cell<u8> = const { Cell::new(0) };
+    }
+
+    fn current_priority() -> u8 {
+        PRIORITY.get()
+    }
+
+    #[test]
+    fn a_writer_with_a_higher_priority_than_the_reader_gets_reported() {
+        assert!(
+            set_priority_callback(current_priority),
+            "no other test registers a callback"
+        );
+        let buffer = LeftRightBuffer::new(0u8);
+
+        // Low Priority Task writes, High Priority Task reads
+        PRIORITY.set(1);
+        buffer.store(1);
+        PRIORITY.set(5);
+        assert_eq!(*buffer.read(), 1);
+
+        // the writer got moved to a task with a higher priority
+        PRIORITY.set(6);
+        assert_eq!(
+            buffer.try_write().err(),
+            Some(crate::Error::Violation(Violation::Priority {
+                writer: 6,
+                reader: 5
+            }))
+        );
+    }
+}
@@ -0,0 +1,134 @@
+//! Cycle counts of the `cortex-m-profiling` feature, see [`StatsSnapshot::cycles`][crate::StatsSnapshot::cycles].
+
+use crate::HoldStats;
+use crate::stats::HoldCells;
+
+/// Cycles spent in the operations of a [`LeftRightBuffer`][crate::LeftRightBuffer].
+///
+/// On Cortex-M targets, they get counted with the DWT cycle counter, which the application has to enable,
+/// e.g. with `DCB::enable_trace()` and `DWT::enable_cycle_counter()` of the cortex-m crate.
+/// On other targets, the registered [`Clock`][crate::Clock] stands in for the cycle counter.
+#[cfg_attr(any(test, not(feature = "fmt-free")), derive(Debug))]
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub struct OperationCycles {
+    /// Acquiring a read guard with [`read()`][crate::LeftRightBuffer::read] or [`try_read()`][crate::LeftRightBuffer::try_read].
+    pub read: HoldStats,
+    /// Acquiring the write guard of the pending side, without the sync.
+    pub write: HoldStats,
+    /// Copying the published value to the pending side, see [`write()`][crate::LeftRightBuffer::write].
+    pub sync: HoldStats,
+    /// Dropping the write guard and swapping the sides, see [`publish()`][crate::LeftRightBuffer::publish].
+    pub publish: HoldStats,
+}
+
+/// Counters of the cycles of each operation.
+pub(crate) struct OperationCells {
+    pub(crate) read: HoldCells,
+    pub(crate) write: HoldCells,
+    pub(crate) sync: HoldCells,
+    pub(crate) publish: HoldCells,
+}
+
+impl OperationCells {
+    pub(crate) const fn new() -> OperationCells {
+        OperationCells {
+            read: HoldCells::new(),
+            write: HoldCells::new(),
+            sync: HoldCells::new(),
+            publish: HoldCells::new(),
+        }
+    }
+
+    pub(crate) fn snapshot(&self) -> OperationCycles {
+        OperationCycles {
+            read: self.read.snapshot(),
+            write: self.write.snapshot(),
+            sync: self.sync.snapshot(),
+            publish: self.publish.snapshot(),
+        }
+    }
+
+    pub(crate) fn take(&self) -> OperationCycles {
+        OperationCycles {
+            read: self.read.take(),
+            write: self.write.take(),
+            sync: self.sync.take(),
+            publish: self.publish.take(),
+        }
+    }
+
+    pub(crate) fn reset(&self) {
+        self.read.reset();
+        self.write.reset();
+        self.sync.reset();
+        self.publish.reset();
+    }
+}
+
+/// Records the cycles until it gets dropped, which is the end of the operation.
+pub(crate) struct CycleTimer<'a> {
+    cells: &'a HoldCells,
+    started: Option<u32>,
+}
+
+impl<'a> CycleTimer<'a> {
+    pub(crate) fn start(cells: &'a HoldCells) -> CycleTimer<'a> {
+        CycleTimer {
+            cells,
+            started: cycles(),
+        }
+    }
+}
+
+impl Drop for CycleTimer<'_> {
+    fn drop(&mut self) {
+        if let (Some(started), Some(now)) = (self.started, cycles()) {
+            self.cells.record(now.wrapping_sub(started));
+        }
+    }
+}
+
+/// Returns the current value of the cycle counter.
+#[cfg(all(target_arch = "arm", target_os = "none"))]
+fn cycles() -> Option<u32> {
+    Some(cortex_m::peripheral::DWT::cycle_count())
+}
+
+/// Returns the time of the registered clock, which stands in for the cycle counter.
+#[cfg(not(all(target_arch = "arm", target_os = "none")))]
+fn cycles() -> Option<u32> {
+    crate::clock::now()
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use crate::LeftRightBuffer;
+    use crate::clock::test_clock;
+
+    #[test]
+    fn every_operation_gets_counted() {
+        test_clock::register();
+        let buffer = LeftRightBuffer::new(0u8);
+        buffer.store(1);
+        let writer = buffer.write();
+        buffer.publish(writer);
+        drop(buffer.read());
+        drop(buffer.try_read());
+
+        let cycles = buffer.stats().cycles;
+        assert_eq!(cycles.read.count, 2);
+        assert_eq!(cycles.write.count, 2);
+        assert_eq!(
+            cycles.sync.count, 1,
+            "only the write after the store synced"
+        );
+        assert_eq!(cycles.publish.count, 2);
+
+        assert_eq!(buffer.take_stats().cycles.read.count, 2);
+        assert_eq!(buffer.stats().cycles.read.count, 0);
+        buffer.store(2);
+        buffer.reset_stats();
+        assert_eq!(buffer.stats().cycles.publish.count, 0);
+    }
+}
@@ -0,0 +1,111 @@
+//! Proof harnesses for the swap protocol, checked with [Kani](https://github.com/model-checking/kani): `cargo kani`
+//!
+//! The harnesses cover every initial state and every payload. A reader interrupting the writer is modeled by reading
+//! while the write guard is held, as a reader never gets interrupted by the writer.
+
+use crate::ordering;
+use crate::{DIRECTION, IN_SYNC, LeftRightBuffer, PUBLISHED};
+
+/// Returns a buffer in an arbitrary, but reachable state with an arbitrary payload.
+fn any_buffer() -> LeftRightBuffer<[u32; 2]> {
+    let buffer = LeftRightBuffer::new(kani::any());
+    if kani::any() {
+        buffer.store(kani::any());
+    }
+    if kani::any() {
+        let mut writer = buffer.write();
+        *writer = kani::any();
+        if kani::any() {
+            buffer.publish(writer);
+        }
+    }
+    buffer
+}
+
+#[kani::proof]
+fn readers_never_observe_a_torn_value() {
+    let buffer = any_buffer();
+    let published = *buffer.read();
+
+    let mut writer = buffer.write();
+    let value: [u32; 2] = kani::any();
+    writer[0] = value[0];
+    // the reader interrupts the writer between the two halves
+    assert_eq!(
+        *buffer.read(),
+        published,
+        "the reader saw the pending value"
+    );
+    writer[1] = value[1];
+    assert_eq!(
+        *buffer.read(),
+        published,
+        "the reader saw the pending value"
+    );
+
+    buffer.publish(writer);
+    assert_eq!(*buffer.read(), value, "the reader missed the publication");
+}
+
+#[kani::proof]
+fn publish_always_flips_to_a_writable_side() {
+    let buffer = any_buffer();
+    let direction = buffer.state.load(ordering::RELAXED) & DIRECTION;
+
+    let writer = buffer.write();
+    buffer.publish(writer);
+
+    let state = buffer.state.load(ordering::RELAXED);
+    assert_ne!(
+        state & DIRECTION,
+        direction,
+        "the publication did not flip the sides"
+    );
+    assert_ne!(state & PUBLISHED, 0, "the publication was not marked");
+    // a reader holding the published side must never block the next writer
+    let reader = buffer.read();
+    assert!(
+        buffer.try_write().is_ok(),
+        "the writer got the published side"
+    );
+    drop(reader);
+}
+
+#[kani::proof]
+fn the_sync_always_copies_from_the_published_side() {
+    let buffer = any_buffer();
+    let mut writer = buffer.write();
+    writer[0] = kani::any();
+    buffer.publish(writer);
+    let published = *buffer.read();
+
+    let writer = buffer.write();
+    assert_eq!(
+        *writer, published,
+        "the pending side differs from the published side"
+    );
+    assert_ne!(
+        buffer.state.load(ordering::RELAXED) & IN_SYNC,
+        0,
+        "the sides are not marked as synced"
+    );
+}
+
+/// With an unwinding bound of 1, the proof fails as soon as one of the hot paths loops or recurses,
+/// so their stack depth and execution time stay bounded for the analysis of an ISR.
+#[kani::proof]
+#[kani::unwind(1)]
+fn the_hot_paths_neither_loop_nor_recurse() {
+    let buffer = LeftRightBuffer::new(kani::any::<u32>());
+    let reader = buffer.read();
+    let mut writer = buffer.write();
+    *writer = kani::any();
+    buffer.publish(writer);
+    drop(reader);
+
+    let value: u32 = kani::any();
+    let mut writer = buffer.write();
+    *writer = value;
+    buffer.publish(writer);
+    assert_eq!(*buffer.read(), value, "the reader missed the publication");
+}
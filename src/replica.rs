@@ -0,0 +1,95 @@
+//! Per-core replicas of the `per-core` feature, see [`PerCore`].
+
+use crate::{Broadcast, ReadGuard};
+
+/// Returns the index of the calling core, e.g. from `MPIDR` or the SIO `CPUID` register.
+pub type CoreIdCallback = fn() -> usize;
+
+static CORE_ID_CALLBACK: spin::Once<CoreIdCallback> = spin::Once::new();
+
+/// Registers the callback, which returns the index of the calling core.
+///
+/// The callback can only be registered once. Returns `false` if there was already a callback.
+/// Without a callback, every reader uses the replica of core 0.
+pub fn set_core_id_callback(callback: CoreIdCallback) -> bool {
+    let mut registered = false;
+    CORE_ID_CALLBACK.call_once(|| {
+        registered = true;
+        callback
+    });
+    registered
+}
+
+/// A value replicated into one buffer per core, so that the readers only access the memory of their own core.
+///
+/// A single writer publishes to all replicas like a [`Broadcast`], the readers pick the replica of their core
+/// with the callback registered with [`set_core_id_callback()`].
+/// Combined with the `cache-padded` feature, the replicas never share a cache line, so reads cause no cross-core traffic.
+pub struct PerCore<T, const CORES: usize> {
+    replicas: Broadcast<T, CORES>,
+}
+
+impl<T: Clone, const CORES: usize> PerCore<T, CORES> {
+    /// Generates a new [`PerCore`], whose replicas start with the data.
+    pub fn new(data: T) -> PerCore<T, CORES> {
+        const { assert!(CORES > 0, "at least one core reads") };
+        PerCore {
+            replicas: Broadcast::new(data),
+        }
+    }
+
+    /// Copies `data` into the replica of every core and publishes it there, see [`Broadcast::publish()`].
+    ///
+    /// Returns the number of replicas, which got the value.
+    pub fn publish(&self, data: &T) -> usize {
+        self.replicas.publish(data)
+    }
+
+    /// Returns a read guard of the replica of the calling core.
+    ///
+    /// Core indices beyond the replicas wrap around, so several cores share a replica if there are less replicas than cores.
+    pub fn read(&self) -> ReadGuard<'_, T> {
+        let core = CORE_ID_CALLBACK.get().map_or(0, |callback| callback());
+        self.replicas
+            .replica(core.checked_rem(CORES).unwrap_or_default())
+            .read()
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+
+    std::thread_local! {
+        static CORE: Cell<usize> = const { Cell::new(0) };
+    }
+
+    fn core_id() -> usize {
+        CORE.with(Cell::get)
+    }
+
+    #[test]
+    fn readers_use_the_replica_of_their_core() {
+        assert!(
+            set_core_id_callback(core_id),
+            "no other test registers a callback"
+        );
+        let rpm = PerCore::<u16, 2>::new(0);
+        assert_eq!(rpm.publish(&900), 2);
+
+        let on_core_0 = rpm.read();
+        CORE.with(|core| core.set(1));
+        let on_core_1 = rpm.read();
+        assert_eq!((*on_core_0, *on_core_1), (900, 900));
+        assert!(
+            !core::ptr::eq(&raw const *on_core_0, &raw const *on_core_1),
+            "every core has its own replica"
+        );
+        drop((on_core_0, on_core_1));
+
+        CORE.with(|core| core.set(3));
+        assert_eq!(*rpm.read(), 900, "core 3 shares the replica of core 1");
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{LeftRightBuffer, ReadGuard, fault};
+use core::ops::Deref;
+
+/// Read guard of the `Ok` value of a `LeftRightBuffer<Result<T, E>>`, see [`read_ok()`][LeftRightBuffer::read_ok].
+pub struct OkReadGuard<'a, T, E> {
+    guard: ReadGuard<'a, Result<T, E>>,
+}
+
+impl<T: Clone, E: Clone> LeftRightBuffer<Result<T, E>> {
+    /// Publishes `Ok(value)`.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, see [`store()`][LeftRightBuffer::store].
+    pub fn publish_ok(&self, value: T) {
+        self.store(Ok(value));
+    }
+
+    /// Publishes `Err(error)`, e.g. to tell the readers that a measurement failed.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, see [`store()`][LeftRightBuffer::store].
+    pub fn publish_err(&self, error: E) {
+        self.store(Err(error));
+    }
+
+    /// Returns a read guard of the published value, if it is `Ok`.
+    ///
+    /// See [`read()`][LeftRightBuffer::read].
+    pub fn read_ok(&self) -> Option<OkReadGuard<'_, T, E>> {
+        let guard = self.read();
+        match *guard {
+            Ok(_) => Some(OkReadGuard { guard }),
+            Err(_) => None,
+        }
+    }
+}
+
+impl<T, E> Deref for OkReadGuard<'_, T, E> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match &*self.guard {
+            Ok(value) => value,
+            Err(_) => fault::unreachable("the guard only gets created for Ok values"),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use crate::LeftRightBuffer;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum SensorError {
+        Timeout,
+    }
+
+    #[test]
+    fn failures_get_propagated_to_the_readers() {
+        let measurement = LeftRightBuffer::new(Err(SensorError::Timeout));
+        assert!(measurement.read_ok().is_none(), "nothing got measured yet");
+
+        measurement.publish_ok(21.5f32);
+        assert_eq!(measurement.read_ok().map(|value| *value), Some(21.5));
+
+        measurement.publish_err(SensorError::Timeout);
+        assert!(measurement.read_ok().is_none(), "the measurement failed");
+        assert_eq!(*measurement.read(), Err(SensorError::Timeout));
+    }
+}
@@ -0,0 +1,76 @@
+//! One-line dumps of the `rtt` feature for the inspection during bring-up.
+
+use crate::LeftRightBuffer;
+use ufmt::{uDebug, uWrite, uwrite};
+
+impl<T: Clone + uDebug> LeftRightBuffer<T> {
+    /// Writes the name, the generation, the publication time and the published value as one line into `channel`.
+    ///
+    /// The `channel` is e.g. an RTT up channel, which implements [`uWrite`], so that the debug probe shows
+    /// what the buffer currently holds. The line looks like `speed gen=3 t=1200 42`,
+    /// where an unnamed buffer shows as `LeftRightBuffer` and `t` is 0 without a clock.
+    ///
+    /// # Errors
+    /// The error of the `channel`.
+    pub fn dump_rtt<W: uWrite + ?Sized>(&self, channel: &mut W) -> Result<(), W::Error> {
+        let (guard, generation) = self.read_with_generation();
+        let name = self.name.unwrap_or("LeftRightBuffer");
+        uwrite!(
+            channel,
+            "{} gen={} t={} {:?}\n",
+            name,
+            generation.get(),
+            guard.published_at().unwrap_or(0),
+            &*guard
+        )
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+    use core::convert::Infallible;
+    use std::string::String;
+
+    /// Collects the lines like an RTT channel.
+    struct Channel(String);
+
+    impl uWrite for Channel {
+        type Error = Infallible;
+
+        fn write_str(&mut self, s: &str) -> Result<(), Infallible> {
+            self.0.push_str(s);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn the_dump_shows_what_the_buffer_holds() {
+        let buffer = LeftRightBuffer::new(0u16).with_name("speed");
+        buffer.store(42);
+        let mut channel = Channel(String::new());
+        buffer.dump_rtt(&mut channel).expect("infallible");
+        assert!(
+            channel.0.starts_with("speed gen=1 t="),
+            "unexpected line {}",
+            channel.0
+        );
+        assert!(
+            channel.0.ends_with(" 42\n"),
+            "unexpected line {}",
+            channel.0
+        );
+
+        let mut channel = Channel(String::new());
+        LeftRightBuffer::new([1u8, 2])
+            .dump_rtt(&mut channel)
+            .expect("infallible");
+        assert!(channel.0.starts_with("LeftRightBuffer gen=0 t="));
+        assert!(
+            channel.0.ends_with(" [1, 2]\n"),
+            "unexpected line {}",
+            channel.0
+        );
+    }
+}
@@ -0,0 +1,111 @@
+//! Scrubbing of both copies of the `zeroize` feature, e.g. for key material or personal data.
+//!
+//! Besides the published value, the buffer holds a shadow copy, which must be scrubbed as well.
+//! Both copies get scrubbed with [`reset()`][LeftRightBuffer::reset] and when the buffer gets dropped,
+//! the shadow copy when the buffer gets [`seal()`][LeftRightBuffer::seal]ed. This covers the entries of a
+//! [`LeftRightHistory`][crate::LeftRightHistory] and the scratch slots of a [`PipelinedBuffer`][crate::PipelinedBuffer] as well.
+//! Memory on the heap, which the payload points to, is out of reach. Use a payload which zeroizes itself on drop for it,
+//! e.g. [`zeroize::Zeroizing`].
+
+use crate::{Counter, LeftRightBuffer, fault};
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::ops::{Deref, DerefMut};
+use core::ptr;
+use zeroize::{Zeroize, zeroize_flat_type};
+
+/// Lock of a copy of the data, whose bytes get overwritten with zeros after it got dropped.
+pub(crate) struct Scrubbed<S>(ManuallyDrop<S>);
+
+/// Wraps the lock of a copy of the data, see [`Scrubbed`].
+pub(crate) const fn scrubbed<S>(lock: S) -> Scrubbed<S> {
+    Scrubbed(ManuallyDrop::new(lock))
+}
+
+impl<S> Deref for Scrubbed<S> {
+    type Target = S;
+
+    fn deref(&self) -> &S {
+        &self.0
+    }
+}
+
+impl<S> DerefMut for Scrubbed<S> {
+    fn deref_mut(&mut self) -> &mut S {
+        &mut self.0
+    }
+}
+
+impl<S> Drop for Scrubbed<S> {
+    fn drop(&mut self) {
+        // SAFETY: the lock gets dropped exactly once here and never accessed afterwards.
+        unsafe { ManuallyDrop::drop(&mut self.0) };
+        // SAFETY: the bytes of the dropped lock are never read as an `S` again, and zeros are valid for `MaybeUninit`.
+        unsafe { zeroize_flat_type(ptr::from_mut(&mut self.0).cast::<MaybeUninit<S>>()) };
+    }
+}
+
+/// Overwrites the bytes of `copy` with zeros and refills it with a clone of `value`.
+pub(crate) fn refill<T: Clone>(copy: &mut T, value: &T) {
+    let value = value.clone();
+    // SAFETY: `copy` gets refilled below before it gets accessed again, so the value is not duplicated.
+    let old = unsafe { ptr::read(copy) };
+    // SAFETY: the moved out bytes are never read as a `T` again, and zeros are valid for `MaybeUninit`.
+    unsafe { zeroize_flat_type(ptr::from_mut(copy).cast::<MaybeUninit<T>>()) };
+    // SAFETY: `copy` is valid for writes and its old value got moved out above.
+    unsafe { ptr::write(copy, value) };
+    drop(old);
+}
+
+impl<T: Clone + Zeroize, G: Counter> LeftRightBuffer<T, G> {
+    /// Scrubs both copies, so that the readers see the zeroized value afterwards.
+    ///
+    /// It counts as a publication, see [`generation()`][LeftRightBuffer::generation].
+    ///
+    /// # Panics
+    /// There is a writer or a reader, the buffer got sealed or the writer has a higher priority than the last reader.
+    pub fn reset(&self) {
+        if let Err(violation) = self.try_overwrite_both(T::zeroize) {
+            fault::violated(violation, self.name, "reset()");
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reset_scrubs_both_copies() {
+        let buffer = LeftRightBuffer::new([0x5au8; 4]);
+        buffer.store([0xa5; 4]);
+        buffer.reset();
+        assert_eq!(*buffer.left.read(), [0; 4]);
+        assert_eq!(*buffer.right.read(), [0; 4]);
+        assert_eq!(buffer.generation(), 2, "the reset counts as a publication");
+    }
+
+    #[test]
+    fn sealing_scrubs_the_pending_copy() {
+        let buffer = LeftRightBuffer::new([0x5au8; 4]);
+        buffer.store([0xa5; 4]);
+        buffer.seal();
+        assert!(buffer.is_sealed(), "the buffer got sealed");
+        assert_eq!(*buffer.read(), [0xa5; 4], "the published copy stays");
+        assert_eq!(
+            *buffer.left.read(),
+            [0xa5; 4],
+            "the pending copy holds nothing but the published value"
+        );
+    }
+
+    #[test]
+    fn dropping_scrubs_the_copy() {
+        let mut copy = MaybeUninit::new(scrubbed([0x5au8; 4]));
+        // SAFETY: the copy got initialized above and gets dropped once.
+        unsafe { copy.assume_init_drop() };
+        // SAFETY: the bytes of the dropped copy got overwritten with zeros, which are valid for `[u8; 4]`.
+        let bytes = unsafe { copy.as_ptr().cast::<[u8; 4]>().read() };
+        assert_eq!(bytes, [0; 4]);
+    }
+}
@@ -0,0 +1,69 @@
+//! Snapshots of the `postcard` feature in the [postcard](https://crates.io/crates/postcard) wire format.
+//!
+//! A snapshot is the postcard encoding of the tuple `(generation, timestamp, value)`,
+//! with the timestamp of the registered [`Clock`][crate::Clock], or 0 without a clock.
+//! Unlike the snapshots of the `snapshot` feature, the payload only needs to implement `serde::Serialize`,
+//! and the varint encoding keeps small numbers small.
+
+use crate::{Error, LeftRightBuffer, clock};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+impl<T: Clone + Serialize> LeftRightBuffer<T> {
+    /// Serializes the published value with its generation and a timestamp into `out` and returns the used part of it.
+    ///
+    /// # Errors
+    /// [`Error::BufferTooSmall`] if the snapshot does not fit into `out`.
+    pub fn to_postcard<'a>(&self, out: &'a mut [u8]) -> Result<&'a [u8], Error> {
+        let (guard, generation) = self.read_with_generation();
+        let timestamp = clock::now().unwrap_or(0);
+        postcard::to_slice(&(generation.get(), timestamp, &*guard), out)
+            .map(|used| &*used)
+            .map_err(|_| Error::BufferTooSmall)
+    }
+}
+
+impl<T: Clone + DeserializeOwned> LeftRightBuffer<T> {
+    /// Restores the value of a snapshot of [`to_postcard()`][LeftRightBuffer::to_postcard] and publishes it.
+    ///
+    /// The generation and the timestamp of the snapshot are not restored, as the generation counts the publications of this buffer.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidSnapshot`] if `bytes` are no postcard snapshot of the payload.
+    /// - [`Error::Violation`] as there is already a writer or the buffer got sealed, see [`try_write_without_sync()`][LeftRightBuffer::try_write_without_sync].
+    pub fn from_postcard(&self, bytes: &[u8]) -> Result<(), Error> {
+        let (_generation, _timestamp, value): (u32, u32, T) =
+            postcard::from_bytes(bytes).map_err(|_| Error::InvalidSnapshot)?;
+        let mut writer = self.try_write_without_sync()?;
+        *writer = value;
+        self.publish(writer);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_postcard_snapshot_gets_restored() {
+        let buffer = LeftRightBuffer::new((1u16, [0i8; 3]));
+        buffer.store((300, [-1, 0, 1]));
+
+        let mut out = [0; 16];
+        let snapshot = buffer.to_postcard(&mut out).expect("the snapshot fits");
+        assert_eq!(snapshot[0], 1, "the generation comes first");
+        let len = snapshot.len();
+        assert_eq!(buffer.to_postcard(&mut [0; 4]), Err(Error::BufferTooSmall));
+
+        let restored = LeftRightBuffer::new((0u16, [0i8; 3]));
+        assert_eq!(restored.from_postcard(&out[..len]), Ok(()));
+        assert_eq!(restored.load(), (300, [-1, 0, 1]));
+        assert_eq!(restored.generation(), 1, "the restore is a publication");
+        assert_eq!(
+            restored.from_postcard(&out[..len - 1]),
+            Err(Error::InvalidSnapshot)
+        );
+    }
+}
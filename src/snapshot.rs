@@ -0,0 +1,110 @@
+//! Snapshots of the `snapshot` feature, e.g. for flight-recorder style black boxes.
+//!
+//! A snapshot consists of a header and the bytes of the published value in the native byte order:
+//!
+//! | Offset | Size | Content |
+//! |---|---|---|
+//! | 0 | 4 | generation, little endian |
+//! | 4 | 4 | timestamp of the registered [`Clock`][crate::Clock], little endian, 0 without a clock |
+//! | 8 | `size_of::<T>()` | published value |
+
+use crate::{Error, LeftRightBuffer, clock};
+use bytemuck::Pod;
+use core::mem::size_of;
+
+/// Size of the header in front of the value.
+const HEADER: usize = 8;
+
+impl<T: Pod> LeftRightBuffer<T> {
+    /// Returns the size of a snapshot of [`dump_snapshot()`][LeftRightBuffer::dump_snapshot] in bytes.
+    #[must_use]
+    pub const fn snapshot_len() -> usize {
+        HEADER + size_of::<T>()
+    }
+
+    /// Writes the published value with its generation and a timestamp into `out`.
+    ///
+    /// Returns the number of written bytes, which is 0 if `out` is shorter than [`snapshot_len()`][LeftRightBuffer::snapshot_len].
+    pub fn dump_snapshot(&self, out: &mut [u8]) -> usize {
+        let Some(out) = out.get_mut(..Self::snapshot_len()) else {
+            return 0;
+        };
+        let (header, value) = out.split_at_mut(HEADER);
+        let (guard, generation) = self.read_with_generation();
+        header[..4].copy_from_slice(&generation.get().to_le_bytes());
+        header[4..].copy_from_slice(&clock::now().unwrap_or(0).to_le_bytes());
+        value.copy_from_slice(bytemuck::bytes_of(&*guard));
+        Self::snapshot_len()
+    }
+
+    /// Restores the value of a snapshot of [`dump_snapshot()`][LeftRightBuffer::dump_snapshot] and publishes it.
+    ///
+    /// The generation and the timestamp of the snapshot are not restored, as the generation counts the publications of this buffer.
+    /// Use it e.g. to resume after a warm reset or to load a persisted configuration at boot.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidSnapshot`] if the length of `bytes` is not [`snapshot_len()`][LeftRightBuffer::snapshot_len].
+    /// - [`Error::Violation`] as there is already a writer or the buffer got sealed, see [`try_write_without_sync()`][LeftRightBuffer::try_write_without_sync].
+    pub fn load_snapshot(&self, bytes: &[u8]) -> Result<(), Error> {
+        let value = Self::snapshot_value(bytes).ok_or(Error::InvalidSnapshot)?;
+        let mut writer = self.try_write_without_sync()?;
+        *writer = value;
+        self.publish(writer);
+        Ok(())
+    }
+
+    /// Returns the value of a snapshot, or `None` if the length of `bytes` is not [`snapshot_len()`][LeftRightBuffer::snapshot_len].
+    pub(crate) fn snapshot_value(bytes: &[u8]) -> Option<T> {
+        if bytes.len() != Self::snapshot_len() {
+            return None;
+        }
+        Some(bytemuck::pod_read_unaligned(&bytes[HEADER..]))
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+    use crate::clock::test_clock;
+
+    #[test]
+    fn the_snapshot_holds_the_published_value() {
+        test_clock::register();
+        test_clock::advance(9);
+        let buffer = LeftRightBuffer::new([1u16, 2]);
+        buffer.store([3, 4]);
+
+        let mut out = [0xff; 16];
+        assert_eq!(buffer.dump_snapshot(&mut out), 12);
+        assert_eq!(out[..4], 1u32.to_le_bytes(), "the generation gets dumped");
+        assert_eq!(out[4..8], 9u32.to_le_bytes(), "the timestamp gets dumped");
+        assert_eq!(out[8..12], *bytemuck::bytes_of(&[3u16, 4]));
+        assert_eq!(out[12..], [0xff; 4], "the rest stays untouched");
+
+        assert_eq!(buffer.dump_snapshot(&mut out[..11]), 0);
+    }
+
+    #[test]
+    fn a_snapshot_gets_restored() {
+        let buffer = LeftRightBuffer::new([1u16, 2]);
+        buffer.store([3, 4]);
+        let mut snapshot = [0; 12];
+        buffer.dump_snapshot(&mut snapshot);
+
+        let restored = LeftRightBuffer::new([0u16, 0]);
+        assert_eq!(restored.load_snapshot(&snapshot), Ok(()));
+        assert_eq!(restored.load(), [3, 4]);
+        assert_eq!(restored.generation(), 1, "the restore is a publication");
+
+        assert_eq!(
+            restored.load_snapshot(&snapshot[..11]),
+            Err(Error::InvalidSnapshot)
+        );
+        restored.seal();
+        assert_eq!(
+            restored.load_snapshot(&snapshot),
+            Err(Error::Violation(crate::Violation::Sealed))
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use crate::LeftRightBuffer;
+use core::mem::{Discriminant, discriminant};
+
+impl<T: Copy + PartialEq> LeftRightBuffer<T> {
+    /// Returns `true` if the published state is the variant of `state`, e.g. `buffer.is_state(discriminant(&Mode::Idle))`.
+    ///
+    /// The data of the variants is ignored.
+    pub fn is_state(&self, state: Discriminant<T>) -> bool {
+        discriminant(&*self.read()) == state
+    }
+
+    /// Replaces the last written state by the result of `f` and publishes it, if it changed.
+    ///
+    /// Returns the new state.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, see [`write()`][LeftRightBuffer::write].
+    pub fn transition(&self, f: impl FnOnce(T) -> T) -> T {
+        let mut writer = self.write();
+        let state = f(*writer);
+        if state != *writer {
+            *writer = state;
+            self.publish(writer);
+        }
+        state
+    }
+
+    /// Same as [`transition()`][LeftRightBuffer::transition], but only the transitions between the variants listed in `table` are legal.
+    ///
+    /// Each entry of the table is a pair of the variants `(from, to)`. Keeping the state is always legal.
+    ///
+    /// # Errors
+    /// Returns the rejected state of an illegal transition. Nothing gets published in this case.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, see [`write()`][LeftRightBuffer::write].
+    pub fn transition_checked(
+        &self,
+        table: &[(Discriminant<T>, Discriminant<T>)],
+        f: impl FnOnce(T) -> T,
+    ) -> Result<T, T> {
+        let mut writer = self.write();
+        let state = f(*writer);
+        if state == *writer {
+            return Ok(state);
+        }
+        let transition = (discriminant(&*writer), discriminant(&state));
+        if !table.contains(&transition) {
+            return Err(state);
+        }
+        *writer = state;
+        self.publish(writer);
+        Ok(state)
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use crate::LeftRightBuffer;
+    use core::mem::discriminant;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Mode {
+        Idle,
+        Run(u8),
+        Fault,
+    }
+
+    #[test]
+    fn transitions_get_published() {
+        let mode = LeftRightBuffer::new(Mode::Idle);
+        assert!(
+            mode.is_state(discriminant(&Mode::Idle)),
+            "the initial state is idle"
+        );
+
+        assert_eq!(mode.transition(|_| Mode::Run(1)), Mode::Run(1));
+        assert!(
+            mode.is_state(discriminant(&Mode::Run(0))),
+            "the data is ignored"
+        );
+        assert_eq!(mode.generation(), 1);
+
+        mode.transition(|state| state);
+        assert_eq!(mode.generation(), 1, "an unchanged state is not published");
+    }
+
+    #[test]
+    fn illegal_transitions_get_rejected() {
+        let table = [
+            (discriminant(&Mode::Idle), discriminant(&Mode::Run(0))),
+            (discriminant(&Mode::Run(0)), discriminant(&Mode::Fault)),
+        ];
+        let mode = LeftRightBuffer::new(Mode::Idle);
+        assert_eq!(
+            mode.transition_checked(&table, |_| Mode::Fault),
+            Err(Mode::Fault)
+        );
+        assert_eq!(*mode.read(), Mode::Idle);
+
+        assert_eq!(
+            mode.transition_checked(&table, |_| Mode::Run(2)),
+            Ok(Mode::Run(2))
+        );
+        assert_eq!(
+            mode.transition_checked(&table, |_| Mode::Run(3)),
+            Err(Mode::Run(3))
+        );
+        assert_eq!(
+            mode.transition_checked(&table, |_| Mode::Fault),
+            Ok(Mode::Fault)
+        );
+        assert_eq!(*mode.read(), Mode::Fault);
+    }
+}
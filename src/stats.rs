@@ -0,0 +1,321 @@
+//! Hold durations and fallback reads of the `stats` feature, see [`stats()`][crate::LeftRightBuffer::stats].
+
+#[cfg(doc)]
+use crate::LeftRightBuffer;
+use crate::clock;
+#[cfg(feature = "cortex-m-profiling")]
+use crate::profiling::{OperationCells, OperationCycles};
+use core::sync::atomic::{AtomicU32, Ordering};
+
+/// Snapshot of the hold durations of the guards of a [`LeftRightBuffer`], see [`stats()`][LeftRightBuffer::stats].
+///
+/// The fields get loaded one after another, so a guard dropped meanwhile may count in `count` but not yet in `min` and `max`
+/// or vice versa. The snapshot is exact as long as no guard gets dropped while it gets taken.
+#[cfg_attr(any(test, not(feature = "fmt-free")), derive(Debug))]
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub struct StatsSnapshot {
+    /// The name of the buffer, see [`with_name()`][LeftRightBuffer::with_name].
+    pub name: Option<&'static str>,
+    /// Hold durations of the read guards.
+    pub read: HoldStats,
+    /// Hold durations of the write guards.
+    pub write: HoldStats,
+    /// Number of reads with [`read()`][LeftRightBuffer::read] or [`read_bounded()`][LeftRightBuffer::read_bounded].
+    ///
+    /// In contrast to `read.count`, it gets counted on acquire and without a clock. It wraps around on overflow.
+    pub reads: u32,
+    /// Number of reads, which took the fallback to the pending side, see [`read()`][LeftRightBuffer::read].
+    ///
+    /// It stays 0 as long as the assumptions are met. Compared to `reads`, it gives the fallback rate.
+    pub fallback_reads: u32,
+    /// Cycles spent in the operations of the buffer.
+    #[cfg(feature = "cortex-m-profiling")]
+    pub cycles: OperationCycles,
+}
+
+impl StatsSnapshot {
+    /// Returns the fallback reads in parts per million of the `reads`, or `None` if there were no reads.
+    #[must_use]
+    pub fn fallback_ppm(&self) -> Option<u32> {
+        let ppm =
+            (u64::from(self.fallback_reads) * 1_000_000).checked_div(u64::from(self.reads))?;
+        Some(u32::try_from(ppm).unwrap_or(u32::MAX))
+    }
+}
+
+/// Hold durations of one kind of guard in ticks of the registered [`Clock`][crate::Clock].
+///
+/// All values are 0 as long as no guard was timed.
+#[cfg_attr(any(test, not(feature = "fmt-free")), derive(Debug))]
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub struct HoldStats {
+    /// Shortest hold duration.
+    pub min: u32,
+    /// Longest hold duration.
+    pub max: u32,
+    /// Mean hold duration, rounded down.
+    pub mean: u32,
+    /// Number of timed guards.
+    pub count: u32,
+}
+
+/// Counters of the hold durations of one kind of guard.
+///
+/// They are only statistics, so relaxed orderings are sufficient.
+pub(crate) struct HoldCells {
+    min: AtomicU32,
+    max: AtomicU32,
+    total: AtomicU32,
+    count: AtomicU32,
+}
+
+impl HoldCells {
+    pub(crate) const fn new() -> HoldCells {
+        HoldCells {
+            min: AtomicU32::new(u32::MAX),
+            max: AtomicU32::new(0),
+            total: AtomicU32::new(0),
+            count: AtomicU32::new(0),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn record(&self, held: u32) {
+        self.min.fetch_min(held, Ordering::Relaxed);
+        self.max.fetch_max(held, Ordering::Relaxed);
+        // the total saturates instead of wrapping, which keeps the mean plausible,
+        // without a compare and swap loop, which would make the drop of a guard unbounded
+        let total = self.total.fetch_add(held, Ordering::Relaxed);
+        if total.checked_add(held).is_none() {
+            self.total.store(u32::MAX, Ordering::Relaxed);
+        }
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> HoldStats {
+        hold_stats(
+            self.min.load(Ordering::Relaxed),
+            self.max.load(Ordering::Relaxed),
+            self.total.load(Ordering::Relaxed),
+            self.count.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Returns the snapshot and resets the counters.
+    ///
+    /// The counters get swapped one after another, so a guard recorded meanwhile may be split between both intervals.
+    pub(crate) fn take(&self) -> HoldStats {
+        let count = self.count.swap(0, Ordering::Relaxed);
+        hold_stats(
+            self.min.swap(u32::MAX, Ordering::Relaxed),
+            self.max.swap(0, Ordering::Relaxed),
+            self.total.swap(0, Ordering::Relaxed),
+            count,
+        )
+    }
+
+    pub(crate) fn reset(&self) {
+        self.count.store(0, Ordering::Relaxed);
+        self.min.store(u32::MAX, Ordering::Relaxed);
+        self.max.store(0, Ordering::Relaxed);
+        self.total.store(0, Ordering::Relaxed);
+    }
+}
+
+/// Returns the hold durations of the counted values.
+///
+/// A guard split between two snapshots may be missing in `min` and `max`, which get widened to the mean,
+/// so that `min <= mean <= max` holds anyway.
+fn hold_stats(min: u32, max: u32, total: u32, count: u32) -> HoldStats {
+    match total.checked_div(count) {
+        Some(mean) => HoldStats {
+            min: min.min(mean),
+            max: max.max(mean),
+            mean,
+            count,
+        },
+        None => HoldStats::default(),
+    }
+}
+
+/// Counters of the read and the write guards and of the fallback reads of a [`LeftRightBuffer`].
+pub(crate) struct GuardStats {
+    pub(crate) read: HoldCells,
+    pub(crate) write: HoldCells,
+    pub(crate) reads: AtomicU32,
+    pub(crate) fallback_reads: AtomicU32,
+    #[cfg(feature = "cortex-m-profiling")]
+    pub(crate) cycles: OperationCells,
+}
+
+impl GuardStats {
+    pub(crate) const fn new() -> GuardStats {
+        GuardStats {
+            read: HoldCells::new(),
+            write: HoldCells::new(),
+            reads: AtomicU32::new(0),
+            fallback_reads: AtomicU32::new(0),
+            #[cfg(feature = "cortex-m-profiling")]
+            cycles: OperationCells::new(),
+        }
+    }
+}
+
+/// Records the hold duration of a guard on drop.
+pub(crate) struct HoldTimer<'a> {
+    cells: &'a HoldCells,
+    acquired: Option<u32>,
+}
+
+impl<'a> HoldTimer<'a> {
+    #[inline]
+    pub(crate) fn start(cells: &'a HoldCells) -> HoldTimer<'a> {
+        HoldTimer {
+            cells,
+            acquired: clock::now(),
+        }
+    }
+}
+
+impl Drop for HoldTimer<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        if let (Some(acquired), Some(now)) = (self.acquired, clock::now()) {
+            self.cells.record(now.wrapping_sub(acquired));
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+    use crate::LeftRightBuffer;
+    use crate::clock::test_clock;
+
+    #[test]
+    fn hold_durations_get_recorded() {
+        test_clock::register();
+        let buffer = LeftRightBuffer::new(1u8);
+        assert_eq!(buffer.stats(), StatsSnapshot::default());
+
+        let reader = buffer.read();
+        test_clock::advance(2);
+        drop(reader);
+        let reader = buffer.read();
+        test_clock::advance(5);
+        drop(reader);
+        let mut writer = buffer.write();
+        test_clock::advance(3);
+        *writer = 2;
+        buffer.publish(writer);
+
+        let stats = buffer.stats();
+        assert_eq!(
+            stats.read,
+            HoldStats {
+                min: 2,
+                max: 5,
+                mean: 3,
+                count: 2
+            }
+        );
+        assert_eq!(
+            stats.write,
+            HoldStats {
+                min: 3,
+                max: 3,
+                mean: 3,
+                count: 1
+            }
+        );
+
+        buffer.reset_stats();
+        assert_eq!(buffer.stats(), StatsSnapshot::default());
+    }
+
+    #[test]
+    fn taking_the_stats_starts_a_new_interval() {
+        test_clock::register();
+        let buffer = LeftRightBuffer::new(1u8);
+        let reader = buffer.read();
+        test_clock::advance(4);
+        drop(reader);
+
+        let interval = buffer.take_stats();
+        assert_eq!(interval.read.max, 4);
+        assert_eq!(interval.read.count, 1);
+        assert_eq!(interval.fallback_ppm(), Some(0));
+        assert_eq!(
+            buffer.stats(),
+            StatsSnapshot::default(),
+            "the next interval starts empty"
+        );
+        assert_eq!(
+            buffer.take_stats().fallback_ppm(),
+            None,
+            "there were no reads"
+        );
+    }
+
+    #[test]
+    fn the_total_saturates_without_a_loop() {
+        let cells = HoldCells::new();
+        cells.record(u32::MAX - 1);
+        cells.record(5);
+        cells.record(5);
+        assert_eq!(
+            cells.snapshot().mean,
+            0x5555_5555,
+            "the total got stuck at the maximum"
+        );
+    }
+
+    #[test]
+    fn a_split_guard_keeps_the_order_of_min_mean_and_max() {
+        // the count and the total of the guard landed in the new interval, its duration in the old one
+        assert_eq!(
+            hold_stats(u32::MAX, 0, 7, 1),
+            HoldStats {
+                min: 7,
+                max: 7,
+                mean: 7,
+                count: 1
+            }
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "test-util")]
+    fn fallback_reads_get_counted() {
+        let buffer = LeftRightBuffer::new(0u8);
+        buffer.store(1);
+        assert_eq!(*buffer.read(), 1);
+        assert_eq!(buffer.stats().fallback_reads, 0);
+
+        let fault = buffer.force_fallback_path();
+        assert_eq!(*buffer.read(), 0);
+        assert_eq!(buffer.read_bounded(3).map(|thing| *thing).ok(), Some(0));
+        drop(fault);
+        let stats = buffer.stats();
+        assert_eq!(stats.fallback_reads, 2);
+        assert_eq!(stats.reads, 3);
+        assert_eq!(stats.fallback_ppm(), Some(666_666));
+
+        buffer.reset_stats();
+        assert_eq!(buffer.stats().fallback_reads, 0);
+        assert_eq!(buffer.stats().fallback_ppm(), None, "there were no reads");
+    }
+
+    #[test]
+    fn the_fallback_rate_needs_no_clock() {
+        // without a clock, no guard gets timed
+        let stats = StatsSnapshot {
+            reads: 4,
+            fallback_reads: 1,
+            ..StatsSnapshot::default()
+        };
+        assert_eq!(stats.read.count, 0);
+        assert_eq!(stats.fallback_ppm(), Some(250_000));
+    }
+}
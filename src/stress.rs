@@ -0,0 +1,176 @@
+//! Soak test of the `test-util` feature with `std`, see [`spawn_readers()`][LeftRightBuffer::spawn_readers].
+
+use crate::LeftRightBuffer;
+use core::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+use std::vec::Vec;
+
+/// Retries of the bounded reads and writes, before the attempt counts as a contention.
+const SPINS: u32 = 64;
+
+/// Counters of a passed soak test, see [`spawn_readers()`][LeftRightBuffer::spawn_readers].
+#[cfg_attr(any(test, not(feature = "fmt-free")), derive(Debug))]
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub struct StressReport {
+    /// Number of values the readers checked.
+    pub reads: u64,
+    /// Number of values the writer published.
+    pub publications: u64,
+    /// Number of reads and writes, which gave up after their retries.
+    pub contentions: u64,
+}
+
+/// Broken invariant found by a soak test, see [`spawn_readers()`][LeftRightBuffer::spawn_readers].
+#[cfg_attr(any(test, not(feature = "fmt-free")), derive(Debug))]
+#[derive(Clone, PartialEq)]
+pub enum StressFailure<T> {
+    /// A reader saw a value, which is not consistent, e.g. half of it got written.
+    Torn(T),
+    /// A reader saw a value, which is older than one it saw before.
+    Regressed {
+        /// The value the reader saw first.
+        before: T,
+        /// The older value the reader saw afterwards.
+        after: T,
+    },
+}
+
+impl<T: Clone + PartialOrd + Send + Sync> LeftRightBuffer<T> {
+    /// Runs a soak test of the buffer for `duration` with `readers` reader threads and one writer thread.
+    ///
+    /// The writer keeps publishing the value following the pending one, which `next` returns and which has to be greater.
+    /// Each reader checks every value it reads with `is_consistent` and verifies that it never sees an older value than before.
+    /// The reads and writes are bounded, see [`read_bounded()`][LeftRightBuffer::read_bounded],
+    /// so a contention on a host with preemptive threads gets counted instead of panicking.
+    ///
+    /// ```
+    /// use minimal_left_right::LeftRightBuffer;
+    /// use std::time::Duration;
+    ///
+    /// // all elements are equal, unless a reader sees a half-written value
+    /// let buffer = LeftRightBuffer::new([0u32; 8]);
+    /// let Ok(report) = buffer.spawn_readers(
+    ///     3,
+    ///     Duration::from_millis(20),
+    ///     |value| value.map(|x| x + 1),
+    ///     |value| value.iter().all(|&x| x == value[0]),
+    /// ) else {
+    ///     panic!("a reader saw a torn or an older value");
+    /// };
+    /// assert!(report.publications > 0);
+    /// ```
+    ///
+    /// # Errors
+    /// The first broken invariant, which stops all threads.
+    pub fn spawn_readers(
+        &self,
+        readers: usize,
+        duration: Duration,
+        mut next: impl FnMut(&T) -> T + Send,
+        is_consistent: impl Fn(&T) -> bool + Sync,
+    ) -> Result<StressReport, StressFailure<T>> {
+        let deadline = Instant::now() + duration;
+        let stop = AtomicBool::new(false);
+        thread::scope(|scope| {
+            let checks: Vec<_> = (0..readers)
+                .map(|_| scope.spawn(|| self.check_reads(&stop, &is_consistent)))
+                .collect();
+            let mut report = StressReport::default();
+            while Instant::now() < deadline && !stop.load(Ordering::Relaxed) {
+                match self.write_bounded(SPINS) {
+                    Ok(mut writer) => {
+                        *writer = next(&writer);
+                        self.publish(writer);
+                        report.publications += 1;
+                    }
+                    Err(_) => report.contentions += 1,
+                }
+            }
+            stop.store(true, Ordering::Relaxed);
+            let mut failure = None;
+            for check in checks {
+                match check.join() {
+                    Ok(Ok((reads, contentions))) => {
+                        report.reads += reads;
+                        report.contentions += contentions;
+                    }
+                    Ok(Err(broken)) => failure = failure.or(Some(broken)),
+                    Err(panic) => std::panic::resume_unwind(panic),
+                }
+            }
+            failure.map_or(Ok(report), Err)
+        })
+    }
+
+    /// Reads until `stop` and returns the number of reads and contentions, or the first broken invariant.
+    fn check_reads(
+        &self,
+        stop: &AtomicBool,
+        is_consistent: &impl Fn(&T) -> bool,
+    ) -> Result<(u64, u64), StressFailure<T>> {
+        let (mut reads, mut contentions) = (0, 0);
+        let mut last = self.load();
+        while !stop.load(Ordering::Relaxed) {
+            let Ok(guard) = self.read_bounded(SPINS) else {
+                contentions += 1;
+                continue;
+            };
+            let value = guard.clone();
+            drop(guard);
+            reads += 1;
+            let broken = if !is_consistent(&value) {
+                StressFailure::Torn(value)
+            } else if value < last {
+                StressFailure::Regressed {
+                    before: last,
+                    after: value,
+                }
+            } else {
+                last = value;
+                continue;
+            };
+            stop.store(true, Ordering::Relaxed);
+            return Err(broken);
+        }
+        Ok((reads, contentions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_soak_test_passes_on_a_correct_buffer() {
+        let buffer = LeftRightBuffer::new((0u64, 0u64));
+        let report = buffer
+            .spawn_readers(
+                4,
+                Duration::from_millis(50),
+                |&(a, b)| (a + 1, b + 1),
+                |&(a, b)| a == b,
+            )
+            .expect("the invariants hold");
+        assert!(report.publications > 0, "the writer published");
+        assert!(report.reads > 0, "the readers read");
+        assert_eq!(buffer.read().0, report.publications);
+    }
+
+    #[test]
+    fn the_soak_test_reports_a_broken_invariant() {
+        let buffer = LeftRightBuffer::new(0u32);
+        let failure = buffer
+            .spawn_readers(
+                1,
+                Duration::from_secs(10),
+                |&value| value + 1,
+                |&value| value < 3,
+            )
+            .expect_err("the readers see 3");
+        assert!(
+            matches!(failure, StressFailure::Torn(value) if value >= 3),
+            "the inconsistent value gets reported"
+        );
+    }
+}
@@ -0,0 +1,117 @@
+//! Status texts of the `heapless` feature, see [`LeftRightString`].
+
+use crate::{LeftRightBuffer, ReadGuard, WriteGuard};
+use core::fmt::{self, Write};
+use heapless::String;
+
+/// Buffer of a text of up to `N` bytes, e.g. a status text shared between a worker task and a display interrupt.
+///
+/// The writer replaces the whole text with [`set_str()`][LeftRightString::set_str] or formats it with
+/// [`set_fmt()`][LeftRightString::set_fmt]. The readers get the published text as `&str`, which is never cut off mid-write.
+///
+/// ```
+/// use minimal_left_right::LeftRightString;
+///
+/// let status = LeftRightString::<16>::new();
+/// assert!(status.set_fmt(format_args!("battery {}%", 87)));
+/// assert_eq!(status.read().as_str(), "battery 87%");
+/// ```
+pub struct LeftRightString<const N: usize> {
+    buffer: LeftRightBuffer<String<N>>,
+}
+
+impl<const N: usize> LeftRightString<N> {
+    /// Generates a new [`LeftRightString`] with an empty text.
+    #[must_use]
+    pub fn new() -> LeftRightString<N> {
+        LeftRightString {
+            buffer: LeftRightBuffer::new_cloned(String::new()),
+        }
+    }
+
+    /// Publishes `text`.
+    ///
+    /// Returns `false` if `text` is longer than `N` bytes. Nothing gets published in this case.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, otherwise it might panic as this could violate the assumptions.
+    pub fn set_str(&self, text: &str) -> bool {
+        let mut published = String::new();
+        if published.push_str(text).is_err() {
+            return false;
+        }
+        self.buffer.store(published);
+        true
+    }
+
+    /// Publishes the formatted text, e.g. of [`format_args!`], which gets formatted on the stack first.
+    ///
+    /// Returns `false` if the text is longer than `N` bytes. Nothing gets published in this case.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, otherwise it might panic as this could violate the assumptions.
+    pub fn set_fmt(&self, args: fmt::Arguments<'_>) -> bool {
+        let mut published = String::new();
+        if published.write_fmt(args).is_err() {
+            return false;
+        }
+        self.buffer.store(published);
+        true
+    }
+
+    /// Returns a write guard of the text, which starts with the published text, see [`LeftRightBuffer::write()`].
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, otherwise it might panic as this could violate the assumptions.
+    pub fn write(&self) -> WriteGuard<'_, String<N>> {
+        self.buffer.write()
+    }
+
+    /// Publishes the written text, see [`LeftRightBuffer::publish()`].
+    ///
+    /// # Panics
+    /// The guard belongs to another buffer or to a side, which got published meanwhile, see [`Violation::ForeignGuard`][crate::Violation::ForeignGuard].
+    pub fn publish(&self, writer: WriteGuard<'_, String<N>>) {
+        self.buffer.publish(writer);
+    }
+
+    /// Returns a read guard of the published text, which dereferences to `&str`.
+    #[must_use]
+    pub fn read(&self) -> ReadGuard<'_, String<N>> {
+        self.buffer.read()
+    }
+}
+
+impl<const N: usize> Default for LeftRightString<N> {
+    fn default() -> LeftRightString<N> {
+        LeftRightString::new()
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn texts_get_published_whole() {
+        let status = LeftRightString::<8>::new();
+        assert!(status.set_str("idle"));
+        assert!(!status.set_str("calibrating"), "the text is too long");
+        assert!(
+            !status.set_fmt(format_args!("step {}", 12_345)),
+            "the text is too long"
+        );
+        assert_eq!(&*status.read(), "idle");
+
+        let mut writer = status.write();
+        assert_eq!(
+            writer.as_str(),
+            "idle",
+            "the writer starts with the published text"
+        );
+        writer.push_str(" 2").expect("the text fits");
+        status.publish(writer);
+        assert_eq!(status.read().as_str(), "idle 2");
+    }
+}
@@ -0,0 +1,185 @@
+//! Synchronization primitives of the [`LeftRightBuffer`][crate::LeftRightBuffer] internals.
+//!
+//! Building with `RUSTFLAGS="--cfg loom"` replaces them by the instrumented primitives of [loom](https://crates.io/crates/loom),
+//! which explores all interleavings of the swap protocol in the models below:
+//!
+//! `RUSTFLAGS="--cfg loom" cargo test --release sync`
+//!
+//! Building with `RUSTFLAGS="--cfg shuttle"` replaces them by the ones of [shuttle](https://crates.io/crates/shuttle),
+//! which runs the same models with more threads and publications under a randomized scheduler:
+//!
+//! `RUSTFLAGS="--cfg shuttle" cargo test --release --features std sync`
+
+#[cfg(not(any(loom, shuttle, feature = "portable-atomic")))]
+pub(crate) use core::sync::atomic::{AtomicU8, AtomicU16, AtomicU32};
+#[cfg(all(feature = "portable-atomic", not(any(loom, shuttle))))]
+pub(crate) use portable_atomic::{AtomicU8, AtomicU16, AtomicU32};
+#[cfg(not(any(loom, shuttle)))]
+pub(crate) use spin::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+#[cfg(loom)]
+use loom::sync as instrumented;
+#[cfg(all(shuttle, not(loom)))]
+use shuttle::sync as instrumented;
+
+#[cfg(any(loom, shuttle))]
+pub(crate) use instrumented::atomic::{AtomicU8, AtomicU16, AtomicU32};
+#[cfg(any(loom, shuttle))]
+pub(crate) use instrumented::{RwLockReadGuard, RwLockWriteGuard};
+
+/// Wrapper offering the API of [`spin::RwLock`] on top of the instrumented lock.
+#[cfg(any(loom, shuttle))]
+pub(crate) struct RwLock<T> {
+    lock: instrumented::RwLock<T>,
+}
+
+#[cfg(any(loom, shuttle))]
+impl<T> RwLock<T> {
+    pub(crate) fn new(data: T) -> RwLock<T> {
+        RwLock {
+            lock: instrumented::RwLock::new(data),
+        }
+    }
+
+    // the hot paths only try to lock, the blocking read is left to the HazardBuffer
+    #[cfg(feature = "strict-ordering")]
+    pub(crate) fn read(&self) -> RwLockReadGuard<'_, T> {
+        self.lock
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    pub(crate) fn try_read(&self) -> Option<RwLockReadGuard<'_, T>> {
+        self.lock.try_read().ok()
+    }
+
+    pub(crate) fn try_write(&self) -> Option<RwLockWriteGuard<'_, T>> {
+        self.lock.try_write().ok()
+    }
+}
+
+#[cfg(test)]
+#[cfg(any(loom, shuttle))]
+mod tests {
+    use crate::LeftRightBuffer;
+    #[cfg(loom)]
+    use loom::{model, sync::Arc, thread};
+    #[cfg(all(shuttle, not(loom)))]
+    use shuttle::{sync::Arc, thread};
+
+    /// Runs the model under a randomized scheduler.
+    #[cfg(all(shuttle, not(loom)))]
+    fn model(f: impl Fn() + Send + Sync + 'static) {
+        shuttle::check_random(f, 1000);
+    }
+
+    #[test]
+    fn readers_never_observe_a_torn_value() {
+        model(|| {
+            let buffer = Arc::new(LeftRightBuffer::new((0u32, 0u32)));
+            let writer = {
+                let buffer = Arc::clone(&buffer);
+                thread::spawn(move || {
+                    for value in 1..=2 {
+                        // without priorities, the reader may hold the pending side, which gets reported instead
+                        if let Ok(mut writer) = buffer.try_write() {
+                            *writer = (value, value);
+                            buffer.publish(writer);
+                        }
+                    }
+                })
+            };
+
+            let (a, b) = *buffer.read();
+            assert_eq!(a, b, "the reader saw a half written value");
+            if let Some(guard) = buffer.try_read() {
+                assert_eq!(guard.0, guard.1, "the reader saw a half written value");
+            }
+            writer.join().expect("the writer never panics");
+        });
+    }
+
+    #[test]
+    fn a_publication_is_visible_to_later_readers() {
+        model(|| {
+            let buffer = Arc::new(LeftRightBuffer::new(0u32));
+            let writer = {
+                let buffer = Arc::clone(&buffer);
+                thread::spawn(move || buffer.store(1))
+            };
+            let reader = {
+                let buffer = Arc::clone(&buffer);
+                thread::spawn(move || {
+                    let value = buffer.load();
+                    assert!(value <= 1, "the reader saw a value which never got written");
+                })
+            };
+
+            writer.join().expect("the writer never panics");
+            reader.join().expect("the reader never panics");
+            assert_eq!(buffer.load(), 1, "the publication got lost");
+            assert_eq!(buffer.generation(), 1, "the publication was not counted");
+        });
+    }
+
+    #[test]
+    fn the_sync_carries_partial_writes_over() {
+        model(|| {
+            let buffer = Arc::new(LeftRightBuffer::new((0u32, 0u32)));
+            let writer = {
+                let buffer = Arc::clone(&buffer);
+                thread::spawn(move || {
+                    let mut writer = buffer.write();
+                    writer.0 = 1;
+                    buffer.publish(writer);
+                    if let Ok(mut writer) = buffer.try_write() {
+                        writer.1 = 1;
+                        buffer.publish(writer);
+                    }
+                })
+            };
+
+            let (a, b) = *buffer.read();
+            assert!(a >= b, "the sync lost the first write");
+            writer.join().expect("the writer never panics");
+            let (a, _) = *buffer.read();
+            assert_eq!(a, 1, "the sync lost the first write");
+        });
+    }
+
+    #[test]
+    #[cfg(all(shuttle, not(loom)))]
+    fn many_readers_never_observe_a_torn_value() {
+        model(|| {
+            let buffer = Arc::new(LeftRightBuffer::new((0u32, 0u32)));
+            let readers: [_; 3] = core::array::from_fn(|_| {
+                let buffer = Arc::clone(&buffer);
+                thread::spawn(move || {
+                    let mut last = 0;
+                    for _ in 0..3 {
+                        let (a, b) = *buffer.read();
+                        assert_eq!(a, b, "the reader saw a half written value");
+                        if let Some(guard) = buffer.try_read() {
+                            assert_eq!(guard.0, guard.1, "the reader saw a half written value");
+                        }
+                        let value = *buffer.read_latest();
+                        assert!(value.0 >= last, "the newest publication went backwards");
+                        last = value.0;
+                    }
+                })
+            });
+
+            for value in 1..=5 {
+                if let Ok(mut writer) = buffer.try_write() {
+                    writer.0 = value;
+                    thread::yield_now();
+                    writer.1 = value;
+                    buffer.publish(writer);
+                }
+            }
+            for reader in readers {
+                reader.join().expect("the reader never panics");
+            }
+        });
+    }
+}
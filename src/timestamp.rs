@@ -0,0 +1,143 @@
+//! Read guards with the publication time of the `clock` feature, see [`read_timestamped()`][crate::LeftRightBuffer::read_timestamped].
+
+use crate::{ReadGuard, clock};
+use core::ops::Deref;
+
+/// Read guard, which knows when its value got published.
+pub struct TimestampedReadGuard<'a, T> {
+    guard: ReadGuard<'a, T>,
+    published_at: Option<u32>,
+    // Mask of the ticks, which the width of the counter of the buffer keeps.
+    mask: u32,
+}
+
+impl<'a, T> TimestampedReadGuard<'a, T> {
+    pub(crate) fn new(
+        guard: ReadGuard<'a, T>,
+        published_at: Option<u32>,
+        mask: u32,
+    ) -> TimestampedReadGuard<'a, T> {
+        TimestampedReadGuard {
+            guard,
+            published_at,
+            mask,
+        }
+    }
+
+    /// Returns the time in ticks of the registered [`Clock`][crate::Clock], when the value got published.
+    ///
+    /// Returns `None` without a clock. With a narrower [`Counter`][crate::Counter] of the buffer, only the low bits of the ticks are kept.
+    #[must_use]
+    pub fn published_at(&self) -> Option<u32> {
+        self.published_at
+    }
+
+    /// Returns the ticks since the publication of the value at the time `now`, e.g. to judge its staleness.
+    ///
+    /// Returns `None` without a clock.
+    #[must_use]
+    pub fn age(&self, now: u32) -> Option<u32> {
+        self.published_at
+            .map(|published_at| now.wrapping_sub(published_at) & self.mask)
+    }
+
+    /// Returns the ticks since the publication of the value until now, see [`age()`][TimestampedReadGuard::age].
+    #[must_use]
+    pub fn age_now(&self) -> Option<u32> {
+        self.age(clock::now()?)
+    }
+
+    /// Returns `true` if the value got published at most `max_age` ticks ago, e.g. before feeding it into a control loop.
+    ///
+    /// Without a clock, the age is unknown and the value never counts as fresh.
+    #[must_use]
+    pub fn is_fresh(&self, max_age: u32) -> bool {
+        self.age_now().is_some_and(|age| age <= max_age)
+    }
+
+    /// Returns `true` if the value got published at most `max_age` ago, see [`is_fresh()`][TimestampedReadGuard::is_fresh].
+    ///
+    /// The registered clock has to be the [`StdClock`][crate::StdClock], so that simulators on the host share the freshness logic of the firmware.
+    #[cfg(feature = "std")]
+    #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+    #[must_use]
+    pub fn is_fresh_within(&self, max_age: std::time::Duration) -> bool {
+        self.is_fresh(crate::StdClock::ticks(max_age))
+    }
+
+    /// Returns the time since the publication of the value until now as a duration in ticks of the registered [`Clock`][crate::Clock].
+    #[cfg(feature = "fugit")]
+    #[must_use]
+    pub fn age_duration<const NOM: u32, const DENOM: u32>(
+        &self,
+    ) -> Option<fugit::Duration<u32, NOM, DENOM>> {
+        self.age_now()
+            .map(fugit::Duration::<u32, NOM, DENOM>::from_ticks)
+    }
+}
+
+impl<T> Deref for TimestampedReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use crate::LeftRightBuffer;
+    use crate::clock::test_clock;
+
+    #[test]
+    fn the_age_counts_from_the_publication() {
+        test_clock::register();
+        let buffer = LeftRightBuffer::new(1u8);
+        test_clock::advance(10);
+        let start = buffer
+            .read_timestamped()
+            .published_at()
+            .expect("a clock is registered");
+
+        buffer.store(2);
+        let published = buffer.read_timestamped();
+        assert_eq!(*published, 2);
+        assert_eq!(published.published_at(), Some(start.wrapping_add(10)));
+        test_clock::advance(7);
+        assert_eq!(published.age_now(), Some(7));
+        assert!(published.is_fresh(7), "the age is within the bound");
+        assert!(!published.is_fresh(6), "the age exceeds the bound");
+        assert!(buffer.is_fresh(7), "the buffer tells the same");
+        #[cfg(feature = "std")]
+        assert!(
+            published.is_fresh_within(std::time::Duration::from_micros(7)),
+            "the test clock counts like the std clock"
+        );
+        #[cfg(feature = "fugit")]
+        assert_eq!(
+            published.age_duration(),
+            Some(fugit::MillisDurationU32::millis(7))
+        );
+        drop(published);
+
+        assert_eq!(
+            buffer.time_since_last_publish(start.wrapping_add(17)),
+            Some(7)
+        );
+        let writer = buffer.write();
+        test_clock::advance(5);
+        buffer.publish(writer);
+        assert_eq!(
+            buffer.read_timestamped().age_now(),
+            Some(0),
+            "the publication renews the value"
+        );
+        assert!(buffer.revert().is_ok(), "the previous value is still there");
+        assert_eq!(
+            buffer.read_timestamped().age_now(),
+            Some(12),
+            "the reverted value keeps its publication time"
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use crate::{LeftRightBuffer, Violation, fault, ordering};
+use core::marker::PhantomData;
+use core::ops::Deref;
+use core::ptr;
+use core::sync::atomic::AtomicBool;
+
+/// State of a [`LeftRight`] before the first publication.
+pub struct Uninit;
+
+/// State of a [`LeftRight`] after the first publication.
+pub struct Ready;
+
+/// Typestate wrapper of a [`LeftRightBuffer`], which can only be read after the first publication.
+///
+/// The placeholder passed to [`new()`][LeftRight::new] never reaches a reader, as reading before
+/// [`publish()`][LeftRight::publish] fails to compile:
+///
+/// ```compile_fail
+/// use minimal_left_right::{LeftRight, Uninit};
+///
+/// let buffer = LeftRight::<Uninit, u32>::new(0);
+/// let _ = buffer.read();
+/// ```
+///
+/// After the first publication, it dereferences to the [`LeftRightBuffer`]:
+///
+/// ```
+/// use minimal_left_right::{LeftRight, Uninit};
+///
+/// let buffer = LeftRight::<Uninit, u32>::new(0).publish(7);
+/// assert_eq!(*buffer.read(), 7);
+/// ```
+///
+/// A `static` shared between a task and an ISR cannot be moved, so it stays `Uninit`. The writer publishes the first value with
+/// [`publish_first()`][LeftRight::publish_first] and the readers get the readable buffer from [`ready()`][LeftRight::ready]:
+///
+/// ```
+/// use minimal_left_right::{LeftRight, Uninit};
+///
+/// static BUFFER: LeftRight<Uninit, u32> = LeftRight::new(0);
+///
+/// // in the ISR, before the first publication
+/// assert!(BUFFER.ready().is_none());
+///
+/// // in the lower priority task
+/// let buffer = BUFFER.publish_first(7);
+/// buffer.store(8);
+///
+/// // in the ISR
+/// if let Some(buffer) = BUFFER.ready() {
+///     assert_eq!(*buffer.read(), 8);
+/// }
+/// ```
+// both states share the layout, so that a reference can change its state
+#[repr(C)]
+pub struct LeftRight<S, T> {
+    buffer: LeftRightBuffer<T>,
+    published: AtomicBool,
+    state: PhantomData<S>,
+}
+
+impl<T: Copy> LeftRight<Uninit, T> {
+    /// Generates a new [`LeftRight`], which holds the `placeholder` until the first publication.
+    ///
+    /// Like [`LeftRightBuffer::new()`], it is const, e.g. for a `static`, and therefore requires [`Copy`].
+    /// For other payloads, see [`new_cloned()`][LeftRight::new_cloned].
+    #[cfg(not(any(loom, shuttle)))]
+    pub const fn new(placeholder: T) -> LeftRight<Uninit, T> {
+        LeftRight {
+            buffer: LeftRightBuffer::new(placeholder),
+            published: AtomicBool::new(false),
+            state: PhantomData,
+        }
+    }
+}
+
+impl<T: Clone> LeftRight<Uninit, T> {
+    /// Generates a new [`LeftRight`] like [`new()`][LeftRight::new], but for payloads which are [`Clone`], see [`LeftRightBuffer::new_cloned()`].
+    pub fn new_cloned(placeholder: T) -> LeftRight<Uninit, T> {
+        LeftRight {
+            buffer: LeftRightBuffer::new_cloned(placeholder),
+            published: AtomicBool::new(false),
+            state: PhantomData,
+        }
+    }
+
+    /// Publishes the first value, which makes the buffer readable.
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, see [`store()`][LeftRightBuffer::store].
+    pub fn publish(self, data: T) -> LeftRight<Ready, T> {
+        self.buffer.store(data);
+        LeftRight {
+            buffer: self.buffer,
+            published: AtomicBool::new(true),
+            state: PhantomData,
+        }
+    }
+
+    /// Publishes the first value like [`publish()`][LeftRight::publish], but keeps the buffer in place, e.g. in a `static`.
+    ///
+    /// Returns the readable buffer, whose lifetime is the one of `self`. The readers get it with [`ready()`][LeftRight::ready].
+    ///
+    /// # Panics
+    /// The first value got published before, see [`Violation::Initialized`].
+    /// This function shall only be called from the lower priority task, see [`store()`][LeftRightBuffer::store].
+    pub fn publish_first(&self, data: T) -> &LeftRight<Ready, T> {
+        // only the writer sets the flag, so it needs no compare and swap
+        if self.published.load(ordering::RELAXED) {
+            fault::violated(Violation::Initialized, self.buffer.name, "publish_first()");
+        }
+        self.buffer.store(data);
+        self.published.store(true, ordering::RELEASE);
+        self.as_ready()
+    }
+}
+
+impl<T> LeftRight<Uninit, T> {
+    /// Returns the readable buffer after the first publication with [`publish_first()`][LeftRight::publish_first], otherwise `None`.
+    pub fn ready(&self) -> Option<&LeftRight<Ready, T>> {
+        self.published
+            .load(ordering::ACQUIRE)
+            .then(|| self.as_ready())
+    }
+
+    /// Returns `self` in the state `Ready`.
+    fn as_ready(&self) -> &LeftRight<Ready, T> {
+        // SAFETY: the states only differ in the zero-sized PhantomData, so both share the layout of the repr(C) struct
+        unsafe { &*ptr::from_ref(self).cast::<LeftRight<Ready, T>>() }
+    }
+}
+
+impl<T> LeftRight<Ready, T> {
+    /// Returns the published buffer.
+    pub fn into_inner(self) -> LeftRightBuffer<T> {
+        self.buffer
+    }
+}
+
+impl<T> Deref for LeftRight<Ready, T> {
+    type Target = LeftRightBuffer<T>;
+
+    fn deref(&self) -> &LeftRightBuffer<T> {
+        &self.buffer
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_static_buffer_gets_ready_in_place() {
+        static BUFFER: LeftRight<Uninit, u8> = LeftRight::new(0);
+        assert!(BUFFER.ready().is_none(), "nothing got published");
+        let buffer = BUFFER.publish_first(3);
+        assert_eq!(*buffer.read(), 3);
+        assert_eq!(BUFFER.ready().map(|buffer| *buffer.read()), Some(3));
+    }
+
+    #[test]
+    #[cfg(not(feature = "panic-free"))] // a violation halts without a handler
+    #[should_panic(expected = "LRBuffer initialized")]
+    fn the_first_value_gets_published_once() {
+        let buffer = LeftRight::<Uninit, _>::new_cloned(0u8);
+        buffer.publish_first(1);
+        buffer.publish_first(2);
+    }
+
+    #[test]
+    fn the_placeholder_never_gets_read() {
+        let buffer = LeftRight::<Uninit, _>::new(0u32).publish(5);
+        assert_eq!(*buffer.read(), 5);
+        assert_eq!(buffer.generation(), 1);
+
+        buffer.store(6);
+        assert_eq!(buffer.into_inner().load(), 6);
+    }
+}
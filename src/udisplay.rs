@@ -0,0 +1,222 @@
+//! [ufmt](https://crates.io/crates/ufmt) formatting of the `ufmt` feature, which avoids the code size of `core::fmt`.
+
+#[cfg(feature = "cortex-m-profiling")]
+use crate::OperationCycles;
+use crate::{Error, LeftRightBuffer, Side, SideInfo, Violation};
+#[cfg(feature = "stats")]
+use crate::{HoldStats, StatsSnapshot};
+use ufmt::{Formatter, uDebug, uDisplay, uWrite, uwrite};
+
+impl uDisplay for Error {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        match self {
+            Error::Violation(violation) => uDisplay::fmt(violation, f),
+            _ => f.write_str(self.message()),
+        }
+    }
+}
+
+impl uDisplay for Violation {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        f.write_str(self.message())?;
+        if let Violation::Priority { writer, reader } = self {
+            uwrite!(f, " {} > {}", writer, reader)?;
+        }
+        Ok(())
+    }
+}
+
+impl uDebug for Error {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        match self {
+            Error::Contention => f.write_str("Contention"),
+            Error::Violation(violation) => f.debug_tuple("Violation")?.field(violation)?.finish(),
+            Error::Poisoned => f.write_str("Poisoned"),
+            Error::InvalidSnapshot => f.write_str("InvalidSnapshot"),
+            Error::BufferTooSmall => f.write_str("BufferTooSmall"),
+            Error::NoPrevious => f.write_str("NoPrevious"),
+        }
+    }
+}
+
+impl uDebug for Violation {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        match self {
+            Violation::Write(side) => f.debug_tuple("Write")?.field(side)?.finish(),
+            Violation::SyncRead(side) => f.debug_tuple("SyncRead")?.field(side)?.finish(),
+            Violation::SyncWrite(side) => f.debug_tuple("SyncWrite")?.field(side)?.finish(),
+            Violation::Priority { writer, reader } => f
+                .debug_struct("Priority")?
+                .field("writer", writer)?
+                .field("reader", reader)?
+                .finish(),
+            Violation::Sealed => f.write_str("Sealed"),
+            Violation::ForeignGuard => f.write_str("ForeignGuard"),
+            Violation::Initialized => f.write_str("Initialized"),
+            Violation::Read => f.write_str("Read"),
+            Violation::SecondWriter => f.write_str("SecondWriter"),
+        }
+    }
+}
+
+impl uDebug for Side {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        f.write_str(match self {
+            Side::Left => "Left",
+            Side::Right => "Right",
+        })
+    }
+}
+
+impl uDebug for SideInfo {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        f.debug_struct("SideInfo")?
+            .field("read_side", &self.read_side)?
+            .field("write_side", &self.write_side)?
+            .field("published_pending", &self.published_pending)?
+            .finish()
+    }
+}
+
+/// Name of a buffer, as ufmt does not implement `uDebug` for `str`.
+struct Name(Option<&'static str>);
+
+impl uDebug for Name {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        match self.0 {
+            Some(name) => uwrite!(f, "Some(\"{}\")", name),
+            None => f.write_str("None"),
+        }
+    }
+}
+
+/// Formats the published value.
+impl<T: Clone + uDebug> uDebug for LeftRightBuffer<T> {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        f.debug_struct("LeftRightBuffer")?
+            .field("name", &Name(self.name))?
+            .field("published", &*self.read())?
+            .finish()
+    }
+}
+
+#[cfg(feature = "stats")]
+impl uDebug for StatsSnapshot {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        let mut stats = f.debug_struct("StatsSnapshot")?;
+        stats
+            .field("name", &Name(self.name))?
+            .field("read", &self.read)?
+            .field("write", &self.write)?
+            .field("reads", &self.reads)?
+            .field("fallback_reads", &self.fallback_reads)?;
+        #[cfg(feature = "cortex-m-profiling")]
+        stats.field("cycles", &self.cycles)?;
+        stats.finish()
+    }
+}
+
+#[cfg(feature = "cortex-m-profiling")]
+impl uDebug for OperationCycles {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        f.debug_struct("OperationCycles")?
+            .field("read", &self.read)?
+            .field("write", &self.write)?
+            .field("sync", &self.sync)?
+            .field("publish", &self.publish)?
+            .finish()
+    }
+}
+
+#[cfg(feature = "stats")]
+impl uDebug for HoldStats {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        f.debug_struct("HoldStats")?
+            .field("min", &self.min)?
+            .field("max", &self.max)?
+            .field("mean", &self.mean)?
+            .field("count", &self.count)?
+            .finish()
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+    use core::convert::Infallible;
+
+    /// Collects the output in a fixed buffer.
+    struct Output {
+        buffer: [u8; 64],
+        len: usize,
+    }
+
+    impl Output {
+        fn new() -> Output {
+            Output {
+                buffer: [0; 64],
+                len: 0,
+            }
+        }
+
+        fn as_str(&self) -> &str {
+            core::str::from_utf8(&self.buffer[..self.len]).expect("ufmt writes UTF-8")
+        }
+    }
+
+    impl uWrite for Output {
+        type Error = Infallible;
+
+        fn write_str(&mut self, s: &str) -> Result<(), Infallible> {
+            self.buffer[self.len..self.len + s.len()].copy_from_slice(s.as_bytes());
+            self.len += s.len();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn errors_get_displayed_like_with_core_fmt() {
+        let mut output = Output::new();
+        uwrite!(
+            output,
+            "{}",
+            Error::Violation(Violation::SyncRead(Side::Right))
+        )
+        .expect("infallible");
+        assert_eq!(output.as_str(), "LRBuffer sync1");
+
+        let mut output = Output::new();
+        uwrite!(
+            output,
+            "{}",
+            Error::Violation(Violation::Priority {
+                writer: 3,
+                reader: 1
+            })
+        )
+        .expect("infallible");
+        assert_eq!(output.as_str(), "LRBuffer priority 3 > 1");
+    }
+
+    #[test]
+    fn the_buffer_shows_the_published_value() {
+        let buffer = LeftRightBuffer::new(1u8).with_name("telemetry");
+        buffer.store(7);
+        let mut output = Output::new();
+        uwrite!(output, "{:?}", buffer).expect("infallible");
+        assert_eq!(
+            output.as_str(),
+            "LeftRightBuffer { name: Some(\"telemetry\"), published: 7 }"
+        );
+
+        let mut output = Output::new();
+        uwrite!(
+            output,
+            "{:?}",
+            Error::Violation(Violation::Write(Side::Left))
+        )
+        .expect("infallible");
+        assert_eq!(output.as_str(), "Violation(Write(Left))");
+    }
+}
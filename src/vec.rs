@@ -0,0 +1,191 @@
+//! Variable-length lists, see [`LeftRightVec`].
+
+use crate::{LeftRightBuffer, ReadGuard, WriteGuard};
+use core::ops::{Deref, DerefMut};
+
+/// List of up to `N` items, whose length is part of the value, so it gets published together with the items.
+///
+/// It dereferences to the slice of the items.
+#[cfg_attr(any(test, not(feature = "fmt-free")), derive(Debug))]
+#[derive(Clone, Copy)]
+pub struct ArrayVec<T, const N: usize> {
+    items: [T; N],
+    len: usize,
+}
+
+impl<T: Copy, const N: usize> ArrayVec<T, N> {
+    /// Appends `item`.
+    ///
+    /// # Errors
+    /// Returns `item` back if the list is full.
+    pub fn push(&mut self, item: T) -> Result<(), T> {
+        let Some(slot) = self.items.get_mut(self.len) else {
+            return Err(item);
+        };
+        *slot = item;
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Removes and returns the last item, or `None` if the list is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        self.len = self.len.checked_sub(1)?;
+        self.items.get(self.len).copied()
+    }
+
+    /// Appends all `items`.
+    ///
+    /// Returns `false` if they do not fit. Nothing gets appended in this case.
+    pub fn extend_from_slice(&mut self, items: &[T]) -> bool {
+        let Some(free) = self.items.get_mut(self.len..self.len + items.len()) else {
+            return false;
+        };
+        free.copy_from_slice(items);
+        self.len += items.len();
+        true
+    }
+
+    /// Shortens the list to `len` items. A longer `len` has no effect.
+    pub fn truncate(&mut self, len: usize) {
+        self.len = self.len.min(len);
+    }
+
+    /// Removes all items.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Returns the maximum number of items.
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+}
+
+impl<T, const N: usize> Deref for ArrayVec<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.items.get(..self.len).unwrap_or_default()
+    }
+}
+
+impl<T, const N: usize> DerefMut for ArrayVec<T, N> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.items.get_mut(..self.len).unwrap_or_default()
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for ArrayVec<T, N> {
+    fn eq(&self, other: &ArrayVec<T, N>) -> bool {
+        **self == **other
+    }
+}
+
+/// Buffer of a variable-length list of up to `N` items, e.g. of the detected objects or of the active faults.
+///
+/// The writer edits the list with [`push()`][ArrayVec::push], [`extend_from_slice()`][ArrayVec::extend_from_slice]
+/// or [`clear()`][ArrayVec::clear] of the write guard, which starts with the published list.
+/// The readers get the published list as a slice, whose length always matches its items.
+///
+/// ```
+/// use minimal_left_right::LeftRightVec;
+///
+/// static FAULTS: LeftRightVec<u16, 8> = LeftRightVec::new(0);
+///
+/// let mut faults = FAULTS.write();
+/// faults.push(17).expect("there is space");
+/// assert!(faults.extend_from_slice(&[23, 42]));
+/// FAULTS.publish(faults);
+/// assert_eq!(**FAULTS.read(), [17, 23, 42]);
+/// ```
+pub struct LeftRightVec<T, const N: usize> {
+    buffer: LeftRightBuffer<ArrayVec<T, N>>,
+}
+
+impl<T: Copy, const N: usize> LeftRightVec<T, N> {
+    /// Generates a new empty [`LeftRightVec`].
+    ///
+    /// The `fill` value occupies the unused slots, which the readers never see.
+    #[cfg(not(any(loom, shuttle)))]
+    pub const fn new(fill: T) -> LeftRightVec<T, N> {
+        LeftRightVec {
+            buffer: LeftRightBuffer::new(ArrayVec {
+                items: [fill; N],
+                len: 0,
+            }),
+        }
+    }
+
+    /// Generates a new empty [`LeftRightVec`].
+    ///
+    /// The `fill` value occupies the unused slots, which the readers never see.
+    #[cfg(any(loom, shuttle))]
+    pub fn new(fill: T) -> LeftRightVec<T, N> {
+        LeftRightVec {
+            buffer: LeftRightBuffer::new(ArrayVec {
+                items: [fill; N],
+                len: 0,
+            }),
+        }
+    }
+
+    /// Returns a write guard of the list, which starts with the published list, see [`LeftRightBuffer::write()`].
+    ///
+    /// # Panics
+    /// This function shall only be called from the lower priority task, otherwise it might panic as this could violate the assumptions.
+    pub fn write(&self) -> WriteGuard<'_, ArrayVec<T, N>> {
+        self.buffer.write()
+    }
+
+    /// Publishes the written list, see [`LeftRightBuffer::publish()`].
+    ///
+    /// # Panics
+    /// The guard belongs to another buffer or to a side, which got published meanwhile, see [`Violation::ForeignGuard`][crate::Violation::ForeignGuard].
+    pub fn publish(&self, writer: WriteGuard<'_, ArrayVec<T, N>>) {
+        self.buffer.publish(writer);
+    }
+
+    /// Returns a read guard of the published list.
+    #[must_use]
+    pub fn read(&self) -> ReadGuard<'_, ArrayVec<T, N>> {
+        self.buffer.read()
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_length_gets_published_with_the_items() {
+        let objects = LeftRightVec::<u8, 3>::new(0);
+        let mut writer = objects.write();
+        assert_eq!(writer.push(1), Ok(()));
+        assert!(writer.extend_from_slice(&[2, 3]));
+        assert_eq!(writer.push(4), Err(4), "the list is full");
+        assert!(!writer.extend_from_slice(&[4]), "the list is full");
+        assert!(objects.read().is_empty(), "the list is pending");
+        objects.publish(writer);
+        assert_eq!(**objects.read(), [1, 2, 3]);
+
+        let mut writer = objects.write();
+        assert_eq!(
+            writer.pop(),
+            Some(3),
+            "the writer starts with the published list"
+        );
+        writer.truncate(1);
+        writer[0] = 9;
+        objects.publish(writer);
+        assert_eq!(**objects.read(), [9]);
+
+        let mut writer = objects.write();
+        writer.clear();
+        assert_eq!(writer.pop(), None);
+        assert_eq!(writer.capacity(), 3);
+        objects.publish(writer);
+        assert!(objects.read().is_empty());
+    }
+}
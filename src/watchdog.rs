@@ -0,0 +1,118 @@
+//! Publish deadlines of the `watchdog` feature, see [`PublishWatchdog`].
+
+use crate::{LeftRightBuffer, clock};
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// Callback of a [`PublishWatchdog`], which gets the name of the buffer and the ticks since its last publication.
+///
+/// It gets invoked once per stall, e.g. to log the stall or to switch to a safe state.
+pub type StallCallback = fn(Option<&'static str>, u32);
+
+/// Reports a stalled writer, which missed the maximum interval between its publications.
+///
+/// A health monitoring task calls [`check()`][PublishWatchdog::check] periodically,
+/// which compares the time since the last publication with the registered [`Clock`][crate::Clock].
+/// The initial value counts as published at tick 0, see [`read_timestamped()`][LeftRightBuffer::read_timestamped].
+pub struct PublishWatchdog<'a, T> {
+    buffer: &'a LeftRightBuffer<T>,
+    max_interval: u32,
+    on_stall: Option<StallCallback>,
+    stalled: AtomicBool,
+}
+
+impl<'a, T: Clone> PublishWatchdog<'a, T> {
+    /// Generates a new [`PublishWatchdog`], which expects a publication of `buffer` at least every `max_interval` ticks.
+    pub fn new(buffer: &'a LeftRightBuffer<T>, max_interval: u32) -> PublishWatchdog<'a, T> {
+        PublishWatchdog {
+            buffer,
+            max_interval,
+            on_stall: None,
+            stalled: AtomicBool::new(false),
+        }
+    }
+
+    /// Generates a new [`PublishWatchdog`] like [`new()`][PublishWatchdog::new] with the maximum interval as a duration
+    /// in ticks of the registered [`Clock`][crate::Clock].
+    #[cfg(feature = "fugit")]
+    pub fn with_deadline<const NOM: u32, const DENOM: u32>(
+        buffer: &'a LeftRightBuffer<T>,
+        max_interval: fugit::Duration<u32, NOM, DENOM>,
+    ) -> PublishWatchdog<'a, T> {
+        PublishWatchdog::new(buffer, max_interval.ticks())
+    }
+
+    /// Attaches a callback, which gets invoked when the writer stalls.
+    #[must_use]
+    pub fn with_callback(mut self, on_stall: StallCallback) -> PublishWatchdog<'a, T> {
+        self.on_stall = Some(on_stall);
+        self
+    }
+
+    /// Checks the deadline and returns `true` if the writer is stalled.
+    ///
+    /// The callback only gets invoked by the check, which detects the stall. The next publication ends the stall.
+    /// Without a clock, the writer never counts as stalled.
+    pub fn check(&self) -> bool {
+        let Some(age) = clock::now().and_then(|now| self.buffer.time_since_last_publish(now))
+        else {
+            return false;
+        };
+        let stalled = age > self.max_interval;
+        let was_stalled = self.stalled.swap(stalled, Ordering::Relaxed);
+        if stalled
+            && !was_stalled
+            && let Some(on_stall) = self.on_stall
+        {
+            on_stall(self.buffer.name(), age);
+        }
+        stalled
+    }
+
+    /// Returns `true` if the last [`check()`][PublishWatchdog::check] found the writer stalled.
+    #[must_use]
+    pub fn is_stalled(&self) -> bool {
+        self.stalled.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(any(loom, shuttle)))]
+mod tests {
+    use super::*;
+    use crate::clock::test_clock;
+    use core::cell::Cell;
+
+    std::thread_local! {
+        static STALLS: Cell<(u32, u32)> = const { Cell::new((0, 0)) };
+    }
+
+    fn count_stall(name: Option<&'static str>, age: u32) {
+        assert_eq!(name, Some("sensor"));
+        STALLS.with(|stalls| stalls.set((stalls.get().0 + 1, age)));
+    }
+
+    #[test]
+    fn a_missed_deadline_gets_reported_once() {
+        test_clock::register();
+        let buffer = LeftRightBuffer::new(0u8).with_name("sensor");
+        buffer.store(1);
+        let watchdog = PublishWatchdog::new(&buffer, 10).with_callback(count_stall);
+        test_clock::advance(10);
+        assert!(!watchdog.check(), "the deadline is not missed yet");
+
+        test_clock::advance(1);
+        assert!(watchdog.check(), "the deadline got missed");
+        assert!(watchdog.is_stalled());
+        test_clock::advance(5);
+        assert!(watchdog.check(), "the writer is still stalled");
+        assert_eq!(
+            STALLS.with(Cell::get),
+            (1, 11),
+            "the callback only reports the start of the stall"
+        );
+
+        buffer.store(2);
+        assert!(!watchdog.check(), "the publication ends the stall");
+        assert!(!watchdog.is_stalled());
+    }
+}
@@ -0,0 +1,30 @@
+//! Cooperative yield of the async functions, which wait without a waker of their own.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+/// Future, which lets the executor run other tasks once before it completes.
+pub(crate) struct YieldNow {
+    yielded: bool,
+}
+
+impl YieldNow {
+    pub(crate) fn new() -> YieldNow {
+        YieldNow { yielded: false }
+    }
+}
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.yielded {
+            return Poll::Ready(());
+        }
+        self.yielded = true;
+        // the executor polls again as soon as the other tasks had their turn
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}